@@ -0,0 +1,81 @@
+use ark_ff::{Fp256, MontBackend, MontConfig};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use gkr::{bristol::from_bristol, protocol::GKRProtocol};
+
+// BN254 scalar field
+#[derive(MontConfig)]
+#[modulus = "21888242871839275222246405745257275088548364400416459932520367421832929095617"]
+#[generator = "5"]
+struct FrConfig;
+type Fr = Fp256<MontBackend<FrConfig, 4>>;
+
+/// An `n`-bit ripple-carry adder in Bristol Fashion, the shape of the published `adder64`.
+fn ripple_carry_adder(n: usize) -> String {
+	let mut gates = vec![];
+	let mut next_wire = 2 * n;
+	let mut wire = || {
+		next_wire += 1;
+		next_wire - 1
+	};
+
+	let mut sums = vec![];
+	let mut carry = None;
+	for i in 0..n {
+		let (a, b) = (i, n + i);
+		let t = wire();
+		gates.push(format!("2 1 {a} {b} {t} XOR"));
+		let and = wire();
+		gates.push(format!("2 1 {a} {b} {and} AND"));
+		match carry {
+			None => {
+				sums.push(t);
+				carry = Some(and);
+			},
+			Some(c) => {
+				let sum = wire();
+				gates.push(format!("2 1 {t} {c} {sum} XOR"));
+				let propagate = wire();
+				gates.push(format!("2 1 {c} {t} {propagate} AND"));
+				let next = wire();
+				gates.push(format!("2 1 {and} {propagate} {next} XOR"));
+				sums.push(sum);
+				carry = Some(next);
+			},
+		}
+	}
+	sums.extend(carry);
+	for sum in sums {
+		let output = wire();
+		gates.push(format!("1 1 {sum} {output} EQW"));
+	}
+
+	format!("{} {}\n2 {n} {n}\n1 {}\n\n{}\n", gates.len(), next_wire, n + 1, gates.join("\n"))
+}
+
+fn bench_bristol(c: &mut Criterion) {
+	let mut group = c.benchmark_group("gkr_bristol");
+	group.sample_size(10);
+
+	for bits in [4, 8, 16] {
+		let bristol = from_bristol(&ripple_carry_adder(bits)).unwrap();
+		let values: Vec<Fr> = (0..2 * bits).map(|i| Fr::from((i % 3 == 0) as u64)).collect();
+		let input = bristol.layout.assign_inputs(&values);
+		let (outputs, proof) = GKRProtocol::prove_with_outputs(&bristol.circuit, &input);
+		assert!(GKRProtocol::verify(&bristol.circuit, &input, &outputs, &proof));
+
+		let id = format!("adder{bits}");
+		group.bench_with_input(BenchmarkId::new("prove", &id), &input, |b, input| {
+			b.iter(|| GKRProtocol::prove(black_box(&bristol.circuit), black_box(input)))
+		});
+		group.bench_with_input(BenchmarkId::new("verify", &id), &proof, |b, proof| {
+			b.iter(|| {
+				GKRProtocol::verify(black_box(&bristol.circuit), &input, &outputs, black_box(proof))
+			})
+		});
+	}
+
+	group.finish();
+}
+
+criterion_group!(benches, bench_bristol);
+criterion_main!(benches);
@@ -0,0 +1,63 @@
+use ark_ff::{Fp256, MontBackend, MontConfig};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use gkr::{
+	datastructure::{Circuit, CircuitLayer, Gate, GateType},
+	libra::LibraProver,
+	protocol::GKRProtocol,
+};
+
+// BN254 scalar field
+#[derive(MontConfig)]
+#[modulus = "21888242871839275222246405745257275088548364400416459932520367421832929095617"]
+#[generator = "5"]
+struct FrConfig;
+type Fr = Fp256<MontBackend<FrConfig, 4>>;
+
+/// Three layers of `2^log_width` alternating add and mul gates, each reading two gates of the
+/// layer below that are far apart.
+fn wide_circuit(log_width: usize) -> Circuit {
+	let width = 1 << log_width;
+	let layer = |shift: usize| {
+		CircuitLayer::new(
+			(0..width)
+				.map(|g| {
+					let gate_type = if g % 2 == 0 { GateType::Add } else { GateType::Mul };
+					Gate::new(gate_type, [g, (g * 7 + shift) % width])
+				})
+				.collect(),
+		)
+	};
+	Circuit::new(vec![layer(1), layer(3), layer(5)])
+}
+
+fn bench_libra(c: &mut Criterion) {
+	let mut group = c.benchmark_group("gkr_prove");
+	group.sample_size(10);
+
+	for log_width in [2, 3, 4, 5, 10] {
+		let circuit = wide_circuit(log_width);
+		let input: Vec<Fr> = (0..1u64 << log_width).map(Fr::from).collect();
+		// the dense prover is cubic in the width of a layer
+		let dense = log_width <= 5;
+		if dense {
+			let (outputs, proof) = LibraProver::prove_with_outputs(&circuit, &input);
+			assert_eq!(proof, GKRProtocol::prove(&circuit, &input));
+			assert!(GKRProtocol::verify(&circuit, &input, &outputs, &proof));
+		}
+
+		let id = format!("width{}", 1 << log_width);
+		group.bench_with_input(BenchmarkId::new("libra", &id), &input, |b, input| {
+			b.iter(|| LibraProver::prove(black_box(&circuit), black_box(input)))
+		});
+		if dense {
+			group.bench_with_input(BenchmarkId::new("dense", &id), &input, |b, input| {
+				b.iter(|| GKRProtocol::prove(black_box(&circuit), black_box(input)))
+			});
+		}
+	}
+
+	group.finish();
+}
+
+criterion_group!(benches, bench_libra);
+criterion_main!(benches);
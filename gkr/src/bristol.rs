@@ -0,0 +1,245 @@
+//! Import of boolean circuits in Bristol Fashion.
+//!
+//! ```text
+//! <gates> <wires>
+//! <number of inputs> <bits of input 0> <bits of input 1> ...
+//! <number of outputs> <bits of output 0> ...
+//!
+//! 2 1 <a> <b> <out> XOR
+//! 2 1 <a> <b> <out> AND
+//! 1 1 <a> <out> INV
+//! ```
+//!
+//! The input bits are wires `0, 1, ...` in order and the output bits are the last wires. `EQW`
+//! copies a wire, `EQ` assigns a constant bit and `MAND` is a batch of `AND`s. Over a prime field
+//! `XOR` becomes `a + b - 2ab`, `AND` becomes `ab` and `INV` becomes `1 - a`, which agree with
+//! the boolean gates on 0/1 wires. The gates are layered by [`CircuitBuilder`].
+
+use crate::{
+	builder::{CircuitBuilder, CircuitLayout, WireId},
+	datastructure::{Circuit, CustomGate, GateType},
+	format::CircuitFormatError,
+};
+
+/// A Bristol Fashion circuit as a layered [`Circuit`].
+#[derive(Debug, Clone)]
+pub struct BristolCircuit {
+	pub circuit: Circuit,
+	/// Maps the input bits, all inputs concatenated, to the circuit input and the output layer to
+	/// the output bits.
+	pub layout: CircuitLayout,
+	/// Bits of each input value.
+	pub inputs: Vec<usize>,
+	/// Bits of each output value.
+	pub outputs: Vec<usize>,
+}
+
+fn syntax(line: usize, reason: &'static str) -> CircuitFormatError {
+	CircuitFormatError::Syntax { line, reason }
+}
+
+fn parse_numbers(line: usize, tokens: &[&str]) -> Result<Vec<usize>, CircuitFormatError> {
+	tokens
+		.iter()
+		.map(|token| token.parse::<usize>())
+		.collect::<Result<_, _>>()
+		.map_err(|_| syntax(line, "expected a number"))
+}
+
+/// `<count> <bits> ...` as the list of bit widths.
+fn parse_widths(line: usize, text: &str) -> Result<Vec<usize>, CircuitFormatError> {
+	let tokens: Vec<&str> = text.split_whitespace().collect();
+	let numbers = parse_numbers(line, &tokens)?;
+	match numbers.split_first() {
+		Some((count, widths)) if *count == widths.len() => Ok(widths.to_vec()),
+		_ => Err(syntax(line, "wire count does not match the number of values")),
+	}
+}
+
+pub fn from_bristol(text: &str) -> Result<BristolCircuit, CircuitFormatError> {
+	let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+	let mut header = |reason| lines.next().ok_or(CircuitFormatError::InvalidCircuit(reason));
+
+	let (line, counts) = header("missing gate and wire counts")?;
+	let counts = parse_numbers(line, &counts.split_whitespace().collect::<Vec<_>>())?;
+	let [number_of_gates, number_of_wires] = counts[..] else {
+		return Err(syntax(line, "expected the gate and wire counts"));
+	};
+	let (line, input_line) = header("missing input widths")?;
+	let inputs = parse_widths(line, input_line)?;
+	let (line, output_line) = header("missing output widths")?;
+	let outputs = parse_widths(line, output_line)?;
+
+	let number_of_inputs: usize = inputs.iter().sum();
+	let number_of_outputs: usize = outputs.iter().sum();
+	if number_of_inputs == 0 || number_of_inputs + number_of_outputs > number_of_wires {
+		return Err(CircuitFormatError::InvalidCircuit(
+			"input and output bits do not fit the wires",
+		));
+	}
+
+	let mut builder = CircuitBuilder::new();
+	let mut wires: Vec<Option<WireId>> = vec![None; number_of_wires];
+	for (wire, input) in wires.iter_mut().zip(builder.inputs(number_of_inputs)) {
+		*wire = Some(input);
+	}
+
+	let mut gate_count = 0;
+	for (line, gate) in lines {
+		let tokens: Vec<&str> = gate.split_whitespace().collect();
+		let Some((operation, numbers)) = tokens.split_last() else { continue };
+		let numbers = parse_numbers(line, numbers)?;
+		let [arity, fan_out, ref labels @ ..] = numbers[..] else {
+			return Err(syntax(line, "expected input and output counts"));
+		};
+		if labels.len() != arity + fan_out {
+			return Err(syntax(line, "wire count does not match the gate"));
+		}
+		let (input_labels, output_labels) = labels.split_at(arity);
+
+		let read = |label: usize| -> Result<WireId, CircuitFormatError> {
+			wires
+				.get(label)
+				.copied()
+				.flatten()
+				.ok_or(syntax(line, "gate reads a wire that is not assigned yet"))
+		};
+		let results = match (*operation, arity, fan_out) {
+			("XOR", 2, 1) => vec![builder.xor(read(input_labels[0])?, read(input_labels[1])?)],
+			("AND", 2, 1) => vec![builder.and(read(input_labels[0])?, read(input_labels[1])?)],
+			("INV", 1, 1) => vec![builder.not(read(input_labels[0])?)],
+			("EQW", 1, 1) => {
+				let wire = read(input_labels[0])?;
+				vec![builder.gate(GateType::Identity, wire, wire)]
+			},
+			("EQ", 1, 1) => {
+				let bit = input_labels[0];
+				if bit > 1 {
+					return Err(syntax(line, "EQ assigns 0 or 1"));
+				}
+				// the label is the bit itself, the gate reads the first input only to have a wire
+				let any = read(0)?;
+				let constant = CustomGate { constant: bit as i64, ..CustomGate::default() };
+				vec![builder.custom(constant, any, any)]
+			},
+			("MAND", _, _) if arity == 2 * fan_out => {
+				let (left, right) = input_labels.split_at(fan_out);
+				left.iter()
+					.zip(right)
+					.map(|(a, b)| Ok(builder.and(read(*a)?, read(*b)?)))
+					.collect::<Result<Vec<_>, CircuitFormatError>>()?
+			},
+			("XOR" | "AND" | "INV" | "EQW" | "EQ" | "MAND", _, _) => {
+				return Err(syntax(line, "wrong number of gate wires"))
+			},
+			_ => return Err(syntax(line, "unknown gate")),
+		};
+
+		for (label, result) in output_labels.iter().zip(results) {
+			let wire = wires.get_mut(*label).ok_or(syntax(line, "wire index out of range"))?;
+			if wire.is_some() {
+				return Err(syntax(line, "wire is assigned twice"));
+			}
+			*wire = Some(result);
+		}
+		gate_count += 1;
+	}
+
+	if gate_count != number_of_gates {
+		return Err(CircuitFormatError::InvalidCircuit("gate count does not match the header"));
+	}
+	for wire in wires[number_of_wires - number_of_outputs..].iter() {
+		builder.output(
+			wire.ok_or(CircuitFormatError::InvalidCircuit("output wire is never assigned"))?,
+		);
+	}
+
+	let (circuit, layout) = builder.build().map_err(CircuitFormatError::InvalidCircuit)?;
+	Ok(BristolCircuit { circuit, layout, inputs, outputs })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::protocol::GKRProtocol;
+	use ark_ff::MontConfig;
+	use ark_ff::{Fp64, MontBackend};
+
+	#[derive(MontConfig)]
+	#[modulus = "17"]
+	#[generator = "3"]
+	struct FqConfig;
+	type Fq = Fp64<MontBackend<FqConfig, 1>>;
+
+	// full adder with a, b the two bits of the first input and the carry in as the second, the
+	// output is (sum, carry out, 1)
+	const FULL_ADDER: &str = "9 13
+2 2 1
+1 3
+
+2 1 0 1 3 XOR
+4 2 0 2 1 3 4 5 MAND
+1 1 4 6 INV
+1 1 5 7 INV
+2 1 6 7 8 AND
+2 1 3 2 9 XOR
+1 1 9 10 EQW
+1 1 8 11 INV
+1 1 1 12 EQ
+";
+
+	#[test]
+	fn test_full_adder() {
+		let bristol = from_bristol(FULL_ADDER).unwrap();
+		assert_eq!(bristol.inputs, vec![2, 1]);
+		assert_eq!(bristol.outputs, vec![3]);
+
+		for bits in 0..8u64 {
+			let values: Vec<Fq> = (0..3).map(|i| Fq::from((bits >> i) & 1)).collect();
+			let input = bristol.layout.assign_inputs(&values);
+			let evaluation = bristol.circuit.evaluate(&input);
+
+			let total = (bits & 1) + ((bits >> 1) & 1) + (bits >> 2);
+			assert_eq!(
+				bristol.layout.read_outputs(&evaluation[0]),
+				vec![Fq::from(total & 1), Fq::from(total >> 1), Fq::from(1)]
+			);
+		}
+	}
+
+	#[test]
+	fn test_prove_imported_circuit() {
+		let bristol = from_bristol(FULL_ADDER).unwrap();
+		let input = bristol.layout.assign_inputs(&[Fq::from(1), Fq::from(0), Fq::from(1)]);
+
+		let (outputs, proof) = GKRProtocol::prove_with_outputs(&bristol.circuit, &input);
+		// 1 + 0 + 1 is sum bit 0 with carry 1, the last output is the constant one
+		assert_eq!(
+			bristol.layout.read_outputs(&outputs),
+			vec![Fq::from(0), Fq::from(1), Fq::from(1)]
+		);
+		assert!(GKRProtocol::verify(&bristol.circuit, &input, &outputs, &proof));
+	}
+
+	#[test]
+	fn test_rejects_malformed_files() {
+		let cases = [
+			("1 3\n1 2\n1 1\n2 1 0 1 2 NAND\n", "unknown gate"),
+			("1 3\n1 2\n1 1\n2 1 0 5 2 XOR\n", "gate reads a wire that is not assigned yet"),
+			("1 3\n1 2\n1 1\n1 1 0 2 XOR\n", "wrong number of gate wires"),
+			("1 3\n1 2\n1 1\n2 1 0 1 1 AND\n", "wire is assigned twice"),
+			("1 3\n1 2\n1 1\n1 1 2 2 EQ\n", "EQ assigns 0 or 1"),
+		];
+		for (text, reason) in cases {
+			assert_eq!(from_bristol(text).err(), Some(syntax(3, reason)));
+		}
+		assert_eq!(
+			from_bristol("1 3\n2 2\n1 1\n2 1 0 1 2 XOR\n").err(),
+			Some(syntax(1, "wire count does not match the number of values"))
+		);
+		assert_eq!(
+			from_bristol("2 3\n1 2\n1 1\n2 1 0 1 2 XOR\n").err(),
+			Some(CircuitFormatError::InvalidCircuit("gate count does not match the header"))
+		);
+	}
+}
@@ -0,0 +1,334 @@
+use crate::datastructure::{Circuit, CircuitLayer, CustomGate, Gate, GateType};
+use ark_ff::PrimeField;
+use std::collections::HashMap;
+
+/// A value in a circuit under construction, an input or the output of a gate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WireId(usize);
+
+#[derive(Debug, Clone)]
+enum Node {
+	Input,
+	Gate { gate_type: GateType, inputs: [WireId; 2] },
+}
+
+/// Builds a layered [`Circuit`] from a DAG of gates.
+///
+/// ```ignore
+/// let mut builder = CircuitBuilder::new();
+/// let [a, b, c] = [builder.input(), builder.input(), builder.input()];
+/// let sum = builder.add(a, b);
+/// let out = builder.mul(sum, c);
+/// builder.output(out);
+/// let (circuit, layout) = builder.build().unwrap();
+/// ```
+///
+/// A gate is placed one layer above the deepest of its inputs. Wires read from more than one
+/// layer down and outputs below the top layer are carried up by `Identity` relay gates, and every
+/// layer is padded with zero gates to a power of two.
+#[derive(Debug, Clone, Default)]
+pub struct CircuitBuilder {
+	nodes: Vec<Node>,
+	inputs: Vec<WireId>,
+	outputs: Vec<WireId>,
+}
+
+/// Where the inputs and outputs of a [`CircuitBuilder`] ended up in the built [`Circuit`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitLayout {
+	/// `inputs[i]` is the position of the `i`-th allocated input in the circuit's input layer.
+	pub inputs: Vec<usize>,
+	/// `outputs[i]` is the position of the `i`-th marked output in the circuit's output layer.
+	pub outputs: Vec<usize>,
+	/// Width of the circuit's input layer, a power of two.
+	pub input_width: usize,
+}
+
+impl CircuitLayout {
+	/// The circuit input for the values of the allocated inputs, in allocation order.
+	pub fn assign_inputs<F: PrimeField>(&self, values: &[F]) -> Vec<F> {
+		assert_eq!(values.len(), self.inputs.len(), "One value per allocated input");
+		let mut input = vec![F::zero(); self.input_width];
+		for (position, value) in self.inputs.iter().zip(values) {
+			input[*position] = *value;
+		}
+		input
+	}
+
+	/// The values of the marked outputs, in marking order, from the circuit's output layer.
+	pub fn read_outputs<F: PrimeField>(&self, output_layer: &[F]) -> Vec<F> {
+		self.outputs.iter().map(|position| output_layer[*position]).collect()
+	}
+}
+
+impl CircuitBuilder {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn input(&mut self) -> WireId {
+		let wire = self.push(Node::Input);
+		self.inputs.push(wire);
+		wire
+	}
+
+	pub fn inputs(&mut self, count: usize) -> Vec<WireId> {
+		(0..count).map(|_| self.input()).collect()
+	}
+
+	pub fn gate(&mut self, gate_type: GateType, left: WireId, right: WireId) -> WireId {
+		assert!(
+			left.0 < self.nodes.len() && right.0 < self.nodes.len(),
+			"Wire does not belong to this builder"
+		);
+		self.push(Node::Gate { gate_type, inputs: [left, right] })
+	}
+
+	pub fn add(&mut self, left: WireId, right: WireId) -> WireId {
+		self.gate(GateType::Add, left, right)
+	}
+
+	pub fn mul(&mut self, left: WireId, right: WireId) -> WireId {
+		self.gate(GateType::Mul, left, right)
+	}
+
+	pub fn sub(&mut self, left: WireId, right: WireId) -> WireId {
+		self.gate(GateType::Sub, left, right)
+	}
+
+	pub fn neg(&mut self, wire: WireId) -> WireId {
+		self.gate(GateType::Neg, wire, wire)
+	}
+
+	pub fn add_const(&mut self, wire: WireId, constant: i64) -> WireId {
+		self.gate(GateType::AddConst(constant), wire, wire)
+	}
+
+	pub fn mul_const(&mut self, wire: WireId, constant: i64) -> WireId {
+		self.gate(GateType::MulConst(constant), wire, wire)
+	}
+
+	pub fn xor(&mut self, left: WireId, right: WireId) -> WireId {
+		self.gate(GateType::Xor, left, right)
+	}
+
+	pub fn and(&mut self, left: WireId, right: WireId) -> WireId {
+		self.gate(GateType::And, left, right)
+	}
+
+	pub fn not(&mut self, wire: WireId) -> WireId {
+		self.gate(GateType::Not, wire, wire)
+	}
+
+	pub fn custom(&mut self, gate: CustomGate, left: WireId, right: WireId) -> WireId {
+		self.gate(GateType::Custom(gate), left, right)
+	}
+
+	/// Marks `wire` as an output of the circuit.
+	pub fn output(&mut self, wire: WireId) {
+		assert!(wire.0 < self.nodes.len(), "Wire does not belong to this builder");
+		self.outputs.push(wire);
+	}
+
+	fn push(&mut self, node: Node) -> WireId {
+		self.nodes.push(node);
+		WireId(self.nodes.len() - 1)
+	}
+
+	/// Layers the DAG into a [`Circuit`] and reports where inputs and outputs were placed.
+	pub fn build(&self) -> Result<(Circuit, CircuitLayout), &'static str> {
+		if self.inputs.is_empty() {
+			return Err("Circuit needs at least one input");
+		}
+		if self.outputs.is_empty() {
+			return Err("Circuit needs at least one output");
+		}
+
+		// nodes only read earlier nodes, so one pass in order computes every depth
+		let mut depths = vec![0; self.nodes.len()];
+		for (index, node) in self.nodes.iter().enumerate() {
+			if let Node::Gate { inputs, .. } = node {
+				depths[index] = 1 + depths[inputs[0].0].max(depths[inputs[1].0]);
+			}
+		}
+		let depth = self.outputs.iter().map(|wire| depths[wire.0]).max().unwrap_or(0).max(1);
+
+		// nodes only read earlier nodes, so one pass in reverse marks everything an output reads
+		let mut reachable = vec![false; self.nodes.len()];
+		for wire in self.outputs.iter() {
+			reachable[wire.0] = true;
+		}
+		for (index, node) in self.nodes.iter().enumerate().rev() {
+			if !reachable[index] {
+				continue;
+			}
+			if let Node::Gate { inputs, .. } = node {
+				reachable[inputs[0].0] = true;
+				reachable[inputs[1].0] = true;
+			}
+		}
+
+		// layers[d] holds the gates at depth d, position[(wire, d)] where a wire sits in layer d
+		let mut layers: Vec<Vec<Gate>> = vec![vec![]; depth + 1];
+		let mut position: HashMap<(WireId, usize), usize> = HashMap::new();
+		for (index, wire) in self.inputs.iter().enumerate() {
+			position.insert((*wire, 0), index);
+		}
+
+		for (index, node) in self.nodes.iter().enumerate() {
+			let Node::Gate { gate_type, inputs } = node else { continue };
+			if !reachable[index] {
+				// not needed for any output
+				continue;
+			}
+			let wire_depth = depths[index];
+			let left = relay(&mut layers, &mut position, &depths, inputs[0], wire_depth - 1);
+			let right = relay(&mut layers, &mut position, &depths, inputs[1], wire_depth - 1);
+			layers[wire_depth].push(Gate::new(gate_type.clone(), [left, right]));
+			position.insert((WireId(index), wire_depth), layers[wire_depth].len() - 1);
+		}
+
+		let outputs = self
+			.outputs
+			.iter()
+			.map(|wire| relay(&mut layers, &mut position, &depths, *wire, depth))
+			.collect();
+
+		for layer in layers.iter_mut().skip(1) {
+			let zero_gate = Gate::new(GateType::Custom(CustomGate::default()), [0, 0]);
+			layer.resize(layer.len().next_power_of_two(), zero_gate);
+		}
+
+		let layout = CircuitLayout {
+			inputs: (0..self.inputs.len()).collect(),
+			outputs,
+			input_width: self.inputs.len().next_power_of_two(),
+		};
+		let circuit =
+			Circuit::new(layers.into_iter().skip(1).rev().map(CircuitLayer::new).collect());
+		Ok((circuit, layout))
+	}
+}
+
+/// Position of `wire` in the layer at `target` depth, adding `Identity` gates that carry it up
+/// from its own layer as needed.
+fn relay(
+	layers: &mut [Vec<Gate>],
+	position: &mut HashMap<(WireId, usize), usize>,
+	depths: &[usize],
+	wire: WireId,
+	target: usize,
+) -> usize {
+	if let Some(index) = position.get(&(wire, target)) {
+		return *index;
+	}
+	assert!(target > depths[wire.0], "Wires are only carried up");
+	let below = relay(layers, position, depths, wire, target - 1);
+	layers[target].push(Gate::unary(GateType::Identity, below));
+	let index = layers[target].len() - 1;
+	position.insert((wire, target), index);
+	index
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::protocol::GKRProtocol;
+	use ark_ff::MontConfig;
+	use ark_ff::{Fp64, MontBackend};
+
+	#[derive(MontConfig)]
+	#[modulus = "17"]
+	#[generator = "3"]
+	struct FqConfig;
+	type Fq = Fp64<MontBackend<FqConfig, 1>>;
+
+	#[test]
+	fn test_builder_layers_and_relays() {
+		// out_0 = (a + b) * c - d, out_1 = 3a
+		let mut builder = CircuitBuilder::new();
+		let [a, b, c, d] = [builder.input(), builder.input(), builder.input(), builder.input()];
+		let sum = builder.add(a, b);
+		let product = builder.mul(sum, c);
+		let difference = builder.sub(product, d);
+		let scaled = builder.mul_const(a, 3);
+		builder.output(difference);
+		builder.output(scaled);
+
+		let (circuit, layout) = builder.build().unwrap();
+		assert_eq!(circuit.layers.len(), 3);
+		// every layer width is a power of two
+		for layer in circuit.layers.iter() {
+			assert!(layer.layer.len().is_power_of_two());
+		}
+		// c is relayed once, d twice, 3a twice
+		let identities = circuit
+			.layers
+			.iter()
+			.flat_map(|layer| layer.layer.iter())
+			.filter(|gate| gate.gate_type == GateType::Identity)
+			.count();
+		assert_eq!(identities, 5);
+
+		let values = [2, 3, 4, 5].map(Fq::from);
+		let input = layout.assign_inputs(&values);
+		let evaluation = circuit.evaluate(&input);
+		assert_eq!(layout.read_outputs(&evaluation[0]), vec![Fq::from(15), Fq::from(6)]);
+
+		let (outputs, proof) = GKRProtocol::prove_with_outputs(&circuit, &input);
+		assert_eq!(outputs, evaluation[0]);
+		assert!(GKRProtocol::verify(&circuit, &input, &outputs, &proof));
+	}
+
+	#[test]
+	fn test_builder_input_as_output_and_shared_wires() {
+		let mut builder = CircuitBuilder::new();
+		let x = builder.inputs(3);
+		let not = builder.not(x[0]);
+		let xor = builder.xor(not, x[1]);
+		builder.output(xor);
+		builder.output(x[2]);
+		builder.output(xor);
+
+		let (circuit, layout) = builder.build().unwrap();
+		assert_eq!(layout.outputs[0], layout.outputs[2]);
+		assert_eq!(layout.input_width, 4);
+
+		let input = layout.assign_inputs(&[Fq::from(1), Fq::from(1), Fq::from(7)]);
+		let outputs = layout.read_outputs(&circuit.evaluate(&input)[0]);
+		assert_eq!(outputs, vec![Fq::from(1), Fq::from(7), Fq::from(1)]);
+	}
+
+	#[test]
+	fn test_builder_skips_gates_no_output_reads() {
+		let build = |with_dead_gates: bool| {
+			let mut builder = CircuitBuilder::new();
+			let [a, b] = [builder.input(), builder.input()];
+			let sum = builder.add(a, b);
+			let product = builder.mul(sum, b);
+			if with_dead_gates {
+				// the same depths as the live gates, but never read by an output
+				let unused = builder.mul(a, b);
+				builder.sub(unused, a);
+			}
+			builder.output(product);
+			builder.build().unwrap().0
+		};
+
+		let widths = |circuit: &Circuit| -> Vec<usize> {
+			circuit.layers.iter().map(|layer| layer.layer.len()).collect()
+		};
+		assert_eq!(widths(&build(true)), widths(&build(false)));
+		assert_eq!(build(true).layers, build(false).layers);
+	}
+
+	#[test]
+	fn test_builder_needs_inputs_and_outputs() {
+		assert_eq!(CircuitBuilder::new().build().err(), Some("Circuit needs at least one input"));
+
+		let mut builder = CircuitBuilder::new();
+		let x = builder.input();
+		builder.neg(x);
+		assert_eq!(builder.build().err(), Some("Circuit needs at least one output"));
+	}
+}
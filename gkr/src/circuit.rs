@@ -1,4 +1,4 @@
-use crate::datastructure::{Circuit, CircuitLayer, Gate, GateType};
+use crate::datastructure::{Circuit, CircuitLayer, CustomGate, Gate, GateType};
 use crate::utils::label_to_binary_to_decimal;
 use ark_ff::PrimeField;
 use polynomial::multilinear::{
@@ -24,10 +24,7 @@ impl Circuit {
 			let temp_layer: Vec<_> = layer
 				.layer
 				.iter()
-				.map(|e| match e.gate_type {
-					GateType::Add => current_input[e.inputs[0]] + current_input[e.inputs[1]],
-					GateType::Mul => current_input[e.inputs[0]] * current_input[e.inputs[1]],
-				})
+				.map(|e| e.gate_type.evaluate(current_input[e.inputs[0]], current_input[e.inputs[1]]))
 				.collect();
 
 			layers.push(temp_layer);
@@ -52,40 +49,111 @@ impl Circuit {
 		&self,
 		layer_index: usize,
 	) -> (MultiLinearPolynomialEvaluationForm<F>, MultiLinearPolynomialEvaluationForm<F>) {
-		let layers = &self.layers[layer_index];
-		let number_of_variables = Circuit::size_of_number_of_variable_at_each_layer(layer_index);
+		let add_i_mle = self.wiring_predicate(layer_index, &GateType::Add);
+		let mul_i_mle = self.wiring_predicate(layer_index, &GateType::Mul);
+		(add_i_mle, mul_i_mle)
+	}
 
-		let mut add_i_evaluations = vec![F::zero(); number_of_variables];
-		let mut mul_i_evaluations = vec![F::zero(); number_of_variables];
+	/// The MLE of `gate_type_i(a, b, c)`, one when gate `a` of layer `layer_index` has type
+	/// `gate_type` and reads `b` and `c`.
+	pub fn wiring_predicate<F: PrimeField>(
+		&self,
+		layer_index: usize,
+		gate_type: &GateType,
+	) -> MultiLinearPolynomialEvaluationForm<F> {
+		let layers = &self.layers[layer_index];
+		let input_variables = self.number_of_variables_at_layer(layer_index + 1);
+		let number_of_variables = self.size_of_number_of_variable_at_each_layer(layer_index);
 
+		let mut evaluations = vec![F::zero(); number_of_variables];
 		for (gate_index, gate) in layers.layer.iter().enumerate() {
-			match gate.gate_type {
-				GateType::Add => {
-					let gate_decimal =
-						label_to_binary_to_decimal(gate_index, gate.inputs[0], gate.inputs[1]);
-					add_i_evaluations[gate_decimal] = F::one();
-				},
-				GateType::Mul => {
-					let gate_decimal =
-						label_to_binary_to_decimal(gate_index, gate.inputs[0], gate.inputs[1]);
-					mul_i_evaluations[gate_decimal] = F::one();
-				},
+			if gate.gate_type == *gate_type {
+				let gate_decimal = label_to_binary_to_decimal(
+					gate_index,
+					gate.inputs[0],
+					gate.inputs[1],
+					input_variables,
+				);
+				evaluations[gate_decimal] = F::one();
 			}
 		}
-		let add_i_mle = MultiLinearPolynomialEvaluationForm::new(add_i_evaluations);
-		let mul_i_mle = MultiLinearPolynomialEvaluationForm::new(mul_i_evaluations);
-		(add_i_mle, mul_i_mle)
+		MultiLinearPolynomialEvaluationForm::new(evaluations)
+	}
+
+	/// One wiring predicate per distinct gate type of layer `layer_index`, in order of first use.
+	pub fn wiring_predicates<F: PrimeField>(
+		&self,
+		layer_index: usize,
+	) -> Vec<(GateType, MultiLinearPolynomialEvaluationForm<F>)> {
+		let mut gate_types: Vec<GateType> = vec![];
+		for gate in self.layers[layer_index].layer.iter() {
+			if !gate_types.contains(&gate.gate_type) {
+				gate_types.push(gate.gate_type.clone());
+			}
+		}
+		gate_types
+			.into_iter()
+			.map(|gate_type| {
+				let predicate = self.wiring_predicate(layer_index, &gate_type);
+				(gate_type, predicate)
+			})
+			.collect()
+	}
+
+	/// Number of values in layer `layer_index`, where `self.layers.len()` is the input layer. The
+	/// width of the input layer is one past the largest input index the last layer reads.
+	pub fn layer_width(&self, layer_index: usize) -> usize {
+		if layer_index < self.layers.len() {
+			return self.layers[layer_index].layer.len();
+		}
+		self.layers
+			.last()
+			.map(|layer| {
+				layer.layer.iter().flat_map(|gate| gate.inputs).max().map_or(0, |index| index + 1)
+			})
+			.unwrap_or(0)
 	}
-	pub fn size_of_number_of_variable_at_each_layer(layer_index: usize) -> usize {
-		if layer_index == 0 {
-			return 1 << 3;
+
+	/// Number of variables of the MLE of layer `layer_index`: the log2 of its width rounded up,
+	/// and at least one.
+	pub fn number_of_variables_at_layer(&self, layer_index: usize) -> usize {
+		number_of_variables_for_width(self.layer_width(layer_index))
+	}
+
+	/// Size of the wiring predicate tables of layer `layer_index`, `2^(a + 2b)` for `a` output
+	/// variables and `b` input variables.
+	pub fn size_of_number_of_variable_at_each_layer(&self, layer_index: usize) -> usize {
+		let output_variables = self.number_of_variables_at_layer(layer_index);
+		let input_variables = self.number_of_variables_at_layer(layer_index + 1);
+		1 << (output_variables + 2 * input_variables)
+	}
+
+	/// Length-prefixed description of the circuit for the transcript. Gates are written as their
+	/// coefficients, which is all the verifier evaluates, and their two inputs.
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		bytes.extend_from_slice(&(self.layers.len() as u64).to_le_bytes());
+		for layer in self.layers.iter() {
+			bytes.extend_from_slice(&(layer.layer.len() as u64).to_le_bytes());
+			for gate in layer.layer.iter() {
+				for coefficient in gate.gate_type.coefficients() {
+					bytes.extend_from_slice(&coefficient.to_le_bytes());
+				}
+				for input in gate.inputs {
+					bytes.extend_from_slice(&(input as u64).to_le_bytes());
+				}
+			}
 		}
-		let layer_index_plus_one = layer_index + 1;
-		let number_of_variable = layer_index + (2 * layer_index_plus_one);
-		1 << number_of_variable
+		bytes
 	}
 }
 
+/// Number of variables needed to index `width` values, the log2 of `width` rounded up, and at
+/// least one.
+pub fn number_of_variables_for_width(width: usize) -> usize {
+	width.max(2).next_power_of_two().trailing_zeros() as usize
+}
+
 impl CircuitLayer {
 	pub fn new(layer: Vec<Gate>) -> Self {
 		CircuitLayer { layer }
@@ -96,6 +164,44 @@ impl Gate {
 	pub fn new(gate_type: GateType, inputs: [usize; 2]) -> Self {
 		Self { gate_type, inputs }
 	}
+
+	/// A gate with one input, e.g. `Neg` or `MulConst`, reading `input` on both sides.
+	pub fn unary(gate_type: GateType, input: usize) -> Self {
+		Self { gate_type, inputs: [input, input] }
+	}
+}
+
+impl GateType {
+	/// The coefficients `[constant, left, right, product]` of `q(x, y)`.
+	pub fn coefficients(&self) -> [i64; 4] {
+		match self {
+			GateType::Add => [0, 1, 1, 0],
+			GateType::Mul | GateType::And => [0, 0, 0, 1],
+			GateType::Sub => [0, 1, -1, 0],
+			GateType::Neg => [0, -1, 0, 0],
+			GateType::Identity => [0, 1, 0, 0],
+			GateType::AddConst(c) => [*c, 1, 0, 0],
+			GateType::MulConst(c) => [0, *c, 0, 0],
+			GateType::Xor => [0, 1, 1, -2],
+			GateType::Not => [1, -1, 0, 0],
+			GateType::Custom(CustomGate { constant, left, right, product }) => {
+				[*constant, *left, *right, *product]
+			},
+		}
+	}
+
+	pub fn evaluate<F: PrimeField>(&self, x: F, y: F) -> F {
+		let [constant, left, right, product] = self.coefficients().map(field_from_i64::<F>);
+		constant + left * x + right * y + product * x * y
+	}
+}
+
+pub fn field_from_i64<F: PrimeField>(value: i64) -> F {
+	if value >= 0 {
+		F::from(value as u64)
+	} else {
+		-F::from(value.unsigned_abs())
+	}
 }
 
 pub fn circuit_layers() -> Circuit {
@@ -186,8 +292,9 @@ mod tests {
 
 	#[test]
 	fn test_label_binary_and_to_decimal() {
-		assert_eq!(label_to_binary_to_decimal(0, 0, 1), 1);
-		assert_eq!(label_to_binary_to_decimal(1, 2, 3), 27);
+		assert_eq!(label_to_binary_to_decimal(0, 0, 1, 2), 1);
+		assert_eq!(label_to_binary_to_decimal(1, 2, 3, 2), 27);
+		assert_eq!(label_to_binary_to_decimal(2, 4, 1, 3), 2 << 6 | 4 << 3 | 1);
 	}
 
 	#[test]
@@ -240,4 +347,97 @@ mod tests {
 			Fq::from(1u32)
 		);
 	}
+
+	#[test]
+	fn test_wiring_widths_follow_layer_sizes() {
+		// three outputs over five gates over six inputs
+		let layer_0 = CircuitLayer::new(vec![
+			Gate::new(GateType::Add, [0, 1]),
+			Gate::new(GateType::Mul, [2, 3]),
+			Gate::new(GateType::Add, [4, 4]),
+		]);
+		let layer_1 = CircuitLayer::new(vec![
+			Gate::new(GateType::Mul, [0, 1]),
+			Gate::new(GateType::Add, [1, 2]),
+			Gate::new(GateType::Mul, [3, 3]),
+			Gate::new(GateType::Add, [4, 5]),
+			Gate::new(GateType::Mul, [5, 0]),
+		]);
+		let circuit = Circuit::new(vec![layer_0, layer_1]);
+
+		assert_eq!(circuit.layer_width(2), 6);
+		assert_eq!(
+			(0..3).map(|i| circuit.number_of_variables_at_layer(i)).collect::<Vec<_>>(),
+			vec![2, 3, 3]
+		);
+		assert_eq!(number_of_variables_for_width(1), 1);
+		assert_eq!(number_of_variables_for_width(4), 2);
+
+		let (add_i_mle, mul_i_mle) = circuit.add_i_mul_ext::<Fq>(0);
+		assert_eq!(add_i_mle.number_of_variables, 8);
+		// gate 2 adds (4, 4): 10 100 100
+		let bits = |value: usize, n: usize| -> Vec<Fq> {
+			(0..n).rev().map(|k| Fq::from(((value >> k) & 1) as u64)).collect()
+		};
+		assert_eq!(add_i_mle.evaluation(&bits(0b10_100_100, 8)), Fq::from(1));
+		assert_eq!(mul_i_mle.evaluation(&bits(0b10_100_100, 8)), Fq::from(0));
+
+		let (_, mul_i_mle) = circuit.add_i_mul_ext::<Fq>(1);
+		assert_eq!(mul_i_mle.number_of_variables, 9);
+		// gate 4 multiplies (5, 0): 100 101 000
+		assert_eq!(mul_i_mle.evaluation(&bits(0b100_101_000, 9)), Fq::from(1));
+	}
+
+	#[test]
+	fn test_richer_gates() {
+		let layer_0 = CircuitLayer::new(vec![
+			Gate::new(GateType::Sub, [0, 1]),
+			Gate::unary(GateType::Neg, 2),
+			Gate::unary(GateType::Identity, 3),
+			Gate::unary(GateType::AddConst(-4), 0),
+			Gate::unary(GateType::MulConst(5), 1),
+			Gate::new(GateType::Xor, [4, 5]),
+			Gate::new(GateType::And, [4, 5]),
+			Gate::unary(GateType::Not, 5),
+			Gate::new(
+				GateType::Custom(CustomGate { constant: 1, left: 2, right: 0, product: 3 }),
+				[2, 2],
+			),
+		]);
+		let circuit = Circuit::new(vec![layer_0]);
+		let input = [7, 3, 2, 9, 1, 0].map(Fq::from);
+
+		assert_eq!(
+			circuit.evaluate(&input)[0],
+			[4, -2, 9, 3, 15, 1, 0, 1, 17].map(field_from_i64::<Fq>).to_vec()
+		);
+	}
+
+	#[test]
+	fn test_wiring_predicates_per_gate_type() {
+		let layer_0 = CircuitLayer::new(vec![
+			Gate::unary(GateType::MulConst(3), 1),
+			Gate::new(GateType::Sub, [0, 1]),
+			Gate::unary(GateType::MulConst(3), 0),
+			Gate::unary(GateType::MulConst(2), 0),
+		]);
+		let circuit = Circuit::new(vec![layer_0]);
+
+		let predicates = circuit.wiring_predicates::<Fq>(0);
+		let gate_types: Vec<GateType> = predicates.iter().map(|(g, _)| g.clone()).collect();
+		assert_eq!(
+			gate_types,
+			vec![GateType::MulConst(3), GateType::Sub, GateType::MulConst(2)]
+		);
+		// gates 0 and 2 share a predicate: 00 1 1 and 10 0 0
+		let ones: Vec<usize> = predicates[0]
+			.1
+			.evaluations
+			.iter()
+			.enumerate()
+			.filter(|(_, e)| **e == Fq::from(1))
+			.map(|(i, _)| i)
+			.collect();
+		assert_eq!(ones, vec![0b0011, 0b1000]);
+	}
 }
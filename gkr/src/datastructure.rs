@@ -1,21 +1,48 @@
 use ark_ff::PrimeField;
-use polynomial::multilinear::evaluation_form::MultiLinearPolynomialEvaluationForm;
 use sum_check::multi_composedsumcheck::ComposedSumcheckProof;
-#[derive(Debug, Clone,PartialEq)]
+/// A gate computes `q(x, y)` of its left input `x` and right input `y`. Gates with one input read
+/// only `x`, boolean gates expect 0/1 wires.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum GateType {
 	Add,
 	Mul,
+	/// `x - y`
+	Sub,
+	/// `-x`
+	Neg,
+	/// `x`, relays a wire to the next layer
+	Identity,
+	/// `x + c`
+	AddConst(i64),
+	/// `c * x`
+	MulConst(i64),
+	/// `x + y - 2xy`
+	Xor,
+	/// `xy`
+	And,
+	/// `1 - x`
+	Not,
+	Custom(CustomGate),
 }
-#[derive(Debug, Clone)]
+/// The degree-2 gate `constant + left * x + right * y + product * x * y`. A square is a product
+/// gate with both inputs wired to the same value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CustomGate {
+	pub constant: i64,
+	pub left: i64,
+	pub right: i64,
+	pub product: i64,
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Gate {
 	pub gate_type: GateType,
 	pub inputs: [usize; 2],
 }
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CircuitLayer {
 	pub layer: Vec<Gate>,
 }
-#[derive(Debug,Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Circuit {
 	pub layers: Vec<CircuitLayer>,
 }
@@ -28,5 +55,4 @@ pub struct GKRProof<F: PrimeField> {
  pub   sumcheck_proofs: Vec<ComposedSumcheckProof<F>>,
   pub  w_i_b: Vec<F>,    // w_mle for layer one onward for rb
   pub  w_i_c: Vec<F>,    // w_mle for layer one onward for rc
-  pub  w_0_mle: MultiLinearPolynomialEvaluationForm<F>, // w_mle for layer
 }
@@ -0,0 +1,544 @@
+//! File formats for layered circuits.
+//!
+//! Both formats list the layers output first, like [`Circuit::layers`], and give the number of
+//! circuit inputs so the last layer can be checked. Gate inputs index the layer below; the last
+//! layer reads the circuit inputs.
+//!
+//! Gate names are `add`, `mul`, `sub`, `xor`, `and` (two inputs), `neg`, `identity`, `not`,
+//! `add_const`, `mul_const` (one input) and `custom`, see [`GateType`]. One input gates may
+//! still carry a second index, which is ignored when evaluating.
+//!
+//! # Text
+//!
+//! ```text
+//! # (x0 + x1) * 3, x2 - x3
+//! gkr 1
+//! inputs 4
+//! layer mul_const(3) 0; identity 1
+//! layer add 0 1; sub 2 3
+//! ```
+//!
+//! A `gkr <version>` line, an `inputs <count>` line, then one `layer` line per layer with its
+//! gates separated by `;`. Constants go in parentheses after the gate name, `custom` takes
+//! `(constant, left, right, product)`. Blank lines and text after `#` are ignored.
+//!
+//! # JSON
+//!
+//! Behind the `json` feature:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "inputs": 4,
+//!   "layers": [
+//!     [{"gate": "mul_const", "constant": 3, "inputs": [0, 0]}, {"gate": "identity", "inputs": [1, 1]}],
+//!     [{"gate": "add", "inputs": [0, 1]}, {"gate": "sub", "inputs": [2, 3]}]
+//!   ]
+//! }
+//! ```
+//!
+//! `add_const` and `mul_const` carry a `constant`, `custom` carries `constant`, `left`, `right`
+//! and `product`.
+
+use crate::datastructure::{Circuit, CircuitLayer, CustomGate, Gate, GateType};
+use std::fmt::{self, Display};
+
+pub const CIRCUIT_FORMAT_VERSION: u64 = 1;
+
+/// Why a circuit file was rejected. Lines, layers and gates are counted from zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitFormatError {
+	/// A line of the text format could not be parsed.
+	Syntax { line: usize, reason: &'static str },
+	/// The JSON document does not have the expected shape.
+	Json(&'static str),
+	/// A gate reads past the end of the layer below.
+	InputOutOfRange { layer: usize, gate: usize, input: usize, width: usize },
+	/// The circuit as a whole is invalid, e.g. it has no layers.
+	InvalidCircuit(&'static str),
+}
+
+impl Display for CircuitFormatError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			CircuitFormatError::Syntax { line, reason } => write!(f, "line {line}: {reason}"),
+			CircuitFormatError::Json(reason) => write!(f, "invalid circuit JSON: {reason}"),
+			CircuitFormatError::InputOutOfRange { layer, gate, input, width } => write!(
+				f,
+				"layer {layer}, gate {gate}: input {input} is out of range for a layer of width {width}"
+			),
+			CircuitFormatError::InvalidCircuit(reason) => write!(f, "invalid circuit: {reason}"),
+		}
+	}
+}
+
+impl std::error::Error for CircuitFormatError {}
+
+/// A circuit together with its number of inputs, which [`Circuit`] itself does not record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitFile {
+	pub number_of_inputs: usize,
+	pub circuit: Circuit,
+}
+
+impl CircuitFile {
+	/// Validates `circuit` against `number_of_inputs` inputs.
+	pub fn new(circuit: Circuit, number_of_inputs: usize) -> Result<Self, CircuitFormatError> {
+		let file = Self { number_of_inputs, circuit };
+		file.validate()?;
+		Ok(file)
+	}
+
+	/// Checks that the circuit has layers, every layer has gates and every gate input is within
+	/// the width of the layer below.
+	pub fn validate(&self) -> Result<(), CircuitFormatError> {
+		let layers = &self.circuit.layers;
+		if layers.is_empty() {
+			return Err(CircuitFormatError::InvalidCircuit("circuit has no layers"));
+		}
+		for (layer_index, layer) in layers.iter().enumerate() {
+			if layer.layer.is_empty() {
+				return Err(CircuitFormatError::InvalidCircuit("layer has no gates"));
+			}
+			let width = match layers.get(layer_index + 1) {
+				Some(below) => below.layer.len(),
+				None => self.number_of_inputs,
+			};
+			for (gate_index, gate) in layer.layer.iter().enumerate() {
+				if let Some(input) = gate.inputs.iter().find(|input| **input >= width) {
+					return Err(CircuitFormatError::InputOutOfRange {
+						layer: layer_index,
+						gate: gate_index,
+						input: *input,
+						width,
+					});
+				}
+			}
+		}
+		Ok(())
+	}
+
+	pub fn from_text(text: &str) -> Result<Self, CircuitFormatError> {
+		let mut version = None;
+		let mut number_of_inputs = None;
+		let mut layers = vec![];
+
+		for (line_index, line) in text.lines().enumerate() {
+			let syntax = |reason| CircuitFormatError::Syntax { line: line_index, reason };
+			let line = line.split('#').next().unwrap_or("").trim();
+			if line.is_empty() {
+				continue;
+			}
+			let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+			match keyword {
+				"gkr" if version.is_none() => {
+					let parsed =
+						rest.trim().parse::<u64>().map_err(|_| syntax("invalid version"))?;
+					if parsed != CIRCUIT_FORMAT_VERSION {
+						return Err(syntax("unsupported format version"));
+					}
+					version = Some(parsed);
+				},
+				"inputs" if version.is_some() && number_of_inputs.is_none() => {
+					let parsed =
+						rest.trim().parse::<usize>().map_err(|_| syntax("invalid input count"))?;
+					number_of_inputs = Some(parsed);
+				},
+				"layer" if number_of_inputs.is_some() => {
+					let gates = rest
+						.split(';')
+						.map(|gate| parse_text_gate(gate.trim()).map_err(syntax))
+						.collect::<Result<Vec<_>, _>>()?;
+					layers.push(CircuitLayer::new(gates));
+				},
+				"gkr" | "inputs" | "layer" => return Err(syntax("unexpected line")),
+				_ => return Err(syntax("unknown keyword")),
+			}
+		}
+
+		let number_of_inputs = number_of_inputs
+			.ok_or(CircuitFormatError::InvalidCircuit("missing `gkr` or `inputs` line"))?;
+		CircuitFile::new(Circuit::new(layers), number_of_inputs)
+	}
+
+	pub fn to_text(&self) -> String {
+		let mut text = format!("gkr {CIRCUIT_FORMAT_VERSION}\ninputs {}\n", self.number_of_inputs);
+		for layer in self.circuit.layers.iter() {
+			let gates: Vec<String> = layer.layer.iter().map(text_gate).collect();
+			text.push_str(&format!("layer {}\n", gates.join("; ")));
+		}
+		text
+	}
+}
+
+impl GateType {
+	pub fn name(&self) -> &'static str {
+		match self {
+			GateType::Add => "add",
+			GateType::Mul => "mul",
+			GateType::Sub => "sub",
+			GateType::Neg => "neg",
+			GateType::Identity => "identity",
+			GateType::AddConst(_) => "add_const",
+			GateType::MulConst(_) => "mul_const",
+			GateType::Xor => "xor",
+			GateType::And => "and",
+			GateType::Not => "not",
+			GateType::Custom(_) => "custom",
+		}
+	}
+
+	/// Whether the gate reads only its left input.
+	pub fn is_unary(&self) -> bool {
+		matches!(
+			self,
+			GateType::Neg
+				| GateType::Identity
+				| GateType::AddConst(_)
+				| GateType::MulConst(_)
+				| GateType::Not
+		)
+	}
+
+	/// The gate called `name` with the given constants, `None` if the name is unknown or the
+	/// number of constants does not fit.
+	pub fn from_name(name: &str, constants: &[i64]) -> Option<Self> {
+		let gate_type = match (name, constants) {
+			("add", []) => GateType::Add,
+			("mul", []) => GateType::Mul,
+			("sub", []) => GateType::Sub,
+			("neg", []) => GateType::Neg,
+			("identity", []) => GateType::Identity,
+			("add_const", [c]) => GateType::AddConst(*c),
+			("mul_const", [c]) => GateType::MulConst(*c),
+			("xor", []) => GateType::Xor,
+			("and", []) => GateType::And,
+			("not", []) => GateType::Not,
+			("custom", [constant, left, right, product]) => GateType::Custom(CustomGate {
+				constant: *constant,
+				left: *left,
+				right: *right,
+				product: *product,
+			}),
+			_ => return None,
+		};
+		Some(gate_type)
+	}
+
+	/// The constants [`GateType::from_name`] expects.
+	pub fn constants(&self) -> Vec<i64> {
+		match self {
+			GateType::AddConst(c) | GateType::MulConst(c) => vec![*c],
+			GateType::Custom(CustomGate { constant, left, right, product }) => {
+				vec![*constant, *left, *right, *product]
+			},
+			_ => vec![],
+		}
+	}
+}
+
+fn parse_text_gate(text: &str) -> Result<Gate, &'static str> {
+	let (head, indices) = match text.find(')') {
+		Some(end) => (&text[..=end], &text[end + 1..]),
+		None => text.split_once(char::is_whitespace).unwrap_or((text, "")),
+	};
+	let (name, constants) = match head.split_once('(') {
+		Some((name, arguments)) => {
+			let arguments = arguments.strip_suffix(')').ok_or("unclosed `(`")?;
+			let constants = arguments
+				.split(',')
+				.map(|c| c.trim().parse::<i64>())
+				.collect::<Result<Vec<_>, _>>()
+				.map_err(|_| "invalid gate constant")?;
+			(name.trim(), constants)
+		},
+		None => (head.trim(), vec![]),
+	};
+	let gate_type = GateType::from_name(name, &constants).ok_or("unknown gate")?;
+
+	let indices = indices
+		.split_whitespace()
+		.map(|index| index.parse::<usize>())
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(|_| "invalid gate input")?;
+	let inputs = match (indices.as_slice(), gate_type.is_unary()) {
+		([left, right], _) => [*left, *right],
+		([input], true) => [*input, *input],
+		_ => return Err("wrong number of gate inputs"),
+	};
+	Ok(Gate::new(gate_type, inputs))
+}
+
+fn text_gate(gate: &Gate) -> String {
+	let mut text = gate.gate_type.name().to_string();
+	let constants = gate.gate_type.constants();
+	if !constants.is_empty() {
+		let constants: Vec<String> = constants.iter().map(|c| c.to_string()).collect();
+		text.push_str(&format!("({})", constants.join(", ")));
+	}
+	if gate.gate_type.is_unary() && gate.inputs[0] == gate.inputs[1] {
+		text.push_str(&format!(" {}", gate.inputs[0]));
+	} else {
+		text.push_str(&format!(" {} {}", gate.inputs[0], gate.inputs[1]));
+	}
+	text
+}
+
+#[cfg(feature = "json")]
+pub mod json {
+	use super::*;
+	use serde_json::{json, Map, Value};
+
+	fn get_usize(value: &Value, reason: &'static str) -> Result<usize, CircuitFormatError> {
+		value.as_u64().map(|v| v as usize).ok_or(CircuitFormatError::Json(reason))
+	}
+
+	/// The keys holding the constants of the gate called `name`, in [`GateType::constants`] order.
+	fn constant_keys(name: &str) -> &'static [&'static str] {
+		match name {
+			"add_const" | "mul_const" => &["constant"],
+			"custom" => &["constant", "left", "right", "product"],
+			_ => &[],
+		}
+	}
+
+	fn gate_to_json(gate: &Gate) -> Value {
+		let mut value = json!({ "gate": gate.gate_type.name(), "inputs": gate.inputs });
+		for (key, constant) in
+			constant_keys(gate.gate_type.name()).iter().zip(gate.gate_type.constants())
+		{
+			value[*key] = json!(constant);
+		}
+		value
+	}
+
+	fn gate_from_json(value: &Value) -> Result<Gate, CircuitFormatError> {
+		let object: &Map<String, Value> =
+			value.as_object().ok_or(CircuitFormatError::Json("gate is not an object"))?;
+		let name = object
+			.get("gate")
+			.and_then(Value::as_str)
+			.ok_or(CircuitFormatError::Json("gate has no name"))?;
+		let keys = constant_keys(name);
+		if object
+			.keys()
+			.any(|key| key != "gate" && key != "inputs" && !keys.contains(&key.as_str()))
+		{
+			return Err(CircuitFormatError::Json("unexpected key for this gate"));
+		}
+		let constants = keys
+			.iter()
+			.map(|key| {
+				object
+					.get(*key)
+					.and_then(Value::as_i64)
+					.ok_or(CircuitFormatError::Json("missing or invalid gate constant"))
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		let gate_type = GateType::from_name(name, &constants)
+			.ok_or(CircuitFormatError::Json("unknown gate or wrong constants"))?;
+
+		let inputs = object
+			.get("inputs")
+			.and_then(Value::as_array)
+			.ok_or(CircuitFormatError::Json("gate has no inputs"))?;
+		let inputs = match inputs.as_slice() {
+			[left, right] => {
+				[get_usize(left, "invalid gate input")?, get_usize(right, "invalid gate input")?]
+			},
+			_ => return Err(CircuitFormatError::Json("gate needs two inputs")),
+		};
+		Ok(Gate::new(gate_type, inputs))
+	}
+
+	impl CircuitFile {
+		pub fn to_json(&self) -> String {
+			let layers: Vec<Value> = self
+				.circuit
+				.layers
+				.iter()
+				.map(|layer| Value::Array(layer.layer.iter().map(gate_to_json).collect()))
+				.collect();
+			json!({
+				"version": CIRCUIT_FORMAT_VERSION,
+				"inputs": self.number_of_inputs,
+				"layers": layers,
+			})
+			.to_string()
+		}
+
+		pub fn from_json(json: &str) -> Result<Self, CircuitFormatError> {
+			let value: Value =
+				serde_json::from_str(json).map_err(|_| CircuitFormatError::Json("invalid JSON"))?;
+			if value["version"].as_u64() != Some(CIRCUIT_FORMAT_VERSION) {
+				return Err(CircuitFormatError::Json("unsupported format version"));
+			}
+			let number_of_inputs = get_usize(&value["inputs"], "missing input count")?;
+			let layers = value["layers"]
+				.as_array()
+				.ok_or(CircuitFormatError::Json("missing layers"))?
+				.iter()
+				.map(|layer| {
+					let gates = layer
+						.as_array()
+						.ok_or(CircuitFormatError::Json("layer is not an array"))?;
+					Ok(CircuitLayer::new(
+						gates.iter().map(gate_from_json).collect::<Result<_, _>>()?,
+					))
+				})
+				.collect::<Result<Vec<_>, CircuitFormatError>>()?;
+
+			CircuitFile::new(Circuit::new(layers), number_of_inputs)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::circuit::circuit_template;
+
+	const SAMPLE: &str = "# (x0 + x1) * 3, x2 - x3
+gkr 1
+inputs 4
+layer mul_const(3) 0; identity 1
+layer add 0 1; sub 2 3
+";
+
+	fn rich_circuit() -> CircuitFile {
+		let layer_0 = CircuitLayer::new(vec![
+			Gate::new(
+				GateType::Custom(CustomGate { constant: -1, left: 2, right: 0, product: 5 }),
+				[0, 1],
+			),
+			Gate::new(GateType::Neg, [1, 0]),
+			Gate::unary(GateType::AddConst(-7), 2),
+		]);
+		let layer_1 = CircuitLayer::new(vec![
+			Gate::new(GateType::Xor, [0, 1]),
+			Gate::new(GateType::And, [1, 2]),
+			Gate::unary(GateType::Not, 2),
+		]);
+		CircuitFile::new(Circuit::new(vec![layer_0, layer_1]), 3).unwrap()
+	}
+
+	#[test]
+	fn test_parse_text() {
+		let file = CircuitFile::from_text(SAMPLE).unwrap();
+		assert_eq!(file.number_of_inputs, 4);
+		assert_eq!(
+			file.circuit,
+			Circuit::new(vec![
+				CircuitLayer::new(vec![
+					Gate::unary(GateType::MulConst(3), 0),
+					Gate::unary(GateType::Identity, 1),
+				]),
+				CircuitLayer::new(vec![
+					Gate::new(GateType::Add, [0, 1]),
+					Gate::new(GateType::Sub, [2, 3]),
+				]),
+			])
+		);
+	}
+
+	#[test]
+	fn test_text_round_trip() {
+		for file in [rich_circuit(), CircuitFile::new(circuit_template(), 4).unwrap()] {
+			let text = file.to_text();
+			assert_eq!(CircuitFile::from_text(&text).unwrap(), file);
+		}
+		assert_eq!(
+			rich_circuit().to_text(),
+			"gkr 1\ninputs 3\nlayer custom(-1, 2, 0, 5) 0 1; neg 1 0; add_const(-7) 2\nlayer xor 0 1; and 1 2; not 2\n"
+		);
+	}
+
+	#[test]
+	fn test_validation() {
+		let out_of_range = "gkr 1\ninputs 4\nlayer add 0 2\nlayer add 0 1; mul 2 3\n";
+		assert_eq!(
+			CircuitFile::from_text(out_of_range),
+			Err(CircuitFormatError::InputOutOfRange { layer: 0, gate: 0, input: 2, width: 2 })
+		);
+		let too_few_inputs = "gkr 1\ninputs 3\nlayer add 0 1; mul 2 3\n";
+		assert_eq!(
+			CircuitFile::from_text(too_few_inputs),
+			Err(CircuitFormatError::InputOutOfRange { layer: 0, gate: 1, input: 3, width: 3 })
+		);
+
+		let errors = [
+			("gkr 2\ninputs 2\nlayer add 0 1\n", 0),
+			("gkr 1\ninputs 2\nlayer pow 0 1\n", 2),
+			("gkr 1\ninputs 2\nlayer add 0\n", 2),
+			("gkr 1\ninputs 2\nlayer mul_const 0\n", 2),
+			("gkr 1\nlayer add 0 1\n", 1),
+		];
+		for (text, line) in errors {
+			assert!(
+				matches!(CircuitFile::from_text(text), Err(CircuitFormatError::Syntax { line: l, .. }) if l == line),
+				"{text}"
+			);
+		}
+		assert_eq!(
+			CircuitFile::from_text("gkr 1\ninputs 2\n"),
+			Err(CircuitFormatError::InvalidCircuit("circuit has no layers"))
+		);
+	}
+
+	#[cfg(feature = "json")]
+	#[test]
+	fn test_json_round_trip() {
+		let file = rich_circuit();
+		let json = file.to_json();
+		assert!(json.contains(r#"{"constant":-7,"gate":"add_const","inputs":[2,2]}"#));
+		assert_eq!(CircuitFile::from_json(&json).unwrap(), file);
+
+		let sample = r#"{"version": 1, "inputs": 4, "layers": [
+			[{"gate": "mul_const", "constant": 3, "inputs": [0, 0]}, {"gate": "identity", "inputs": [1, 1]}],
+			[{"gate": "add", "inputs": [0, 1]}, {"gate": "sub", "inputs": [2, 3]}]
+		]}"#;
+		assert_eq!(
+			CircuitFile::from_json(sample).unwrap(),
+			CircuitFile::from_text(SAMPLE).unwrap()
+		);
+
+		let bad = r#"{"version": 1, "inputs": 1, "layers": [[{"gate": "add", "inputs": [0, 1]}]]}"#;
+		assert!(matches!(
+			CircuitFile::from_json(bad),
+			Err(CircuitFormatError::InputOutOfRange { input: 1, .. })
+		));
+	}
+
+	#[cfg(feature = "json")]
+	#[test]
+	fn test_json_rejects_misplaced_and_unknown_keys() {
+		let with_gate = |gate: &str| {
+			format!(
+				r#"{{"version": 1, "inputs": 2, "layers": [[{gate}, {{"gate": "add", "inputs": [0, 1]}}]]}}"#
+			)
+		};
+		assert!(CircuitFile::from_json(&with_gate(
+			r#"{"gate": "add_const", "constant": 5, "inputs": [0, 0]}"#
+		))
+		.is_ok());
+
+		for gate in [
+			// a custom gate's key on a constant gate
+			r#"{"gate": "add_const", "left": 5, "inputs": [0, 0]}"#,
+			r#"{"gate": "mul_const", "constant": 2, "product": 1, "inputs": [0, 0]}"#,
+			// stray keys on gates without constants
+			r#"{"gate": "add", "constant": 1, "inputs": [0, 1]}"#,
+			r#"{"gate": "mul", "extra": true, "inputs": [0, 1]}"#,
+			// a custom gate needs all four constants
+			r#"{"gate": "custom", "constant": 1, "left": 2, "right": 3, "inputs": [0, 1]}"#,
+		] {
+			assert!(
+				matches!(
+					CircuitFile::from_json(&with_gate(gate)),
+					Err(CircuitFormatError::Json(_))
+				),
+				"{gate}"
+			);
+		}
+	}
+}
@@ -3,4 +3,9 @@ pub mod circuit;
 pub mod utils;
 pub mod wire;
 pub mod interfaces;
-pub mod protocol;
\ No newline at end of file
+pub mod protocol;
+pub mod builder;
+pub mod format;
+pub mod bristol;
+pub mod serialization;
+pub mod libra;
\ No newline at end of file
@@ -0,0 +1,275 @@
+//! Libra's linear-time GKR prover (Xie et al., CRYPTO 2019).
+//!
+//! The sum-check of a layer proves `sum_{b, c} f(b, c)` for
+//! `f(b, c) = sum_g w(g) * eq(b, in_0(g)) * eq(c, in_1(g)) * q_g(W(b), W(c))`, where `W` is the
+//! MLE of the layer below and the weights are `eq(r, g)` on the output layer and
+//! `alpha * eq(r_b, g) + beta * eq(r_c, g)` below it. With `q_g(x, y) = k_0 + k_1 x + k_2 y +
+//! k_3 x y`, summing over `c` first leaves
+//!
+//! ```text
+//! h_1(b) = A(b) + B(b) * W(b)
+//! A[x] = sum_{g : in_0(g) = x} w(g) * (k_0 + k_2 * W(in_1(g)))
+//! B[x] = sum_{g : in_0(g) = x} w(g) * (k_1 + k_3 * W(in_1(g)))
+//! ```
+//!
+//! and once the first phase has bound `b` to `u`
+//!
+//! ```text
+//! h_2(c) = C(c) + D(c) * W(c)
+//! C[y] = sum_{g : in_1(g) = y} w(g) * eq(u, in_0(g)) * (k_0 + k_1 * W(u))
+//! D[y] = sum_{g : in_1(g) = y} w(g) * eq(u, in_0(g)) * (k_2 + k_3 * W(u))
+//! ```
+//!
+//! Every table has one entry per value of the layer below and is filled in one pass over the
+//! gates, so a layer takes time linear in its gates and the width of the layer below, where
+//! [`GKRProtocol::prove`] builds wiring predicates of size `2^(k_i + 2 k_(i+1))`.
+//!
+//! The round polynomials are the ones of the dense prover, the proofs are checked by
+//! [`GKRProtocol::verify`].
+//!
+//! [`GKRProtocol::prove`]: crate::protocol::GKRProtocol::prove
+//! [`GKRProtocol::verify`]: crate::protocol::GKRProtocol::verify
+
+use crate::{
+	circuit::field_from_i64,
+	datastructure::{Circuit, CircuitLayer, GKRProof},
+	protocol::statement_transcript,
+	utils::{absorb_layer_claims, layer_sumcheck_statement, pad_layer, w_mle},
+};
+use ark_ff::PrimeField;
+use polynomial::{
+	composed::{
+		interface::ComposedMultilinearInterface, multilinear::ComposedMultiLinearPolynomial,
+	},
+	multilinear::{
+		evaluation_form::MultiLinearPolynomialEvaluationForm,
+		interface::MultiLinearPolynomialEvaluationFormTrait, utils::eq_mle,
+	},
+	univariate_polynomial::univariate::UnivariatePolynomial,
+};
+use sum_check::{
+	interactive::{fiat_shamir_challenge, RoundMessage, SumcheckProverState},
+	multi_composedsumcheck::ComposedSumcheckProof,
+	statement::SumcheckStatement,
+};
+use transcript::transcription::Transcript;
+
+pub struct LibraProver;
+
+impl LibraProver {
+	pub fn prove<F: PrimeField>(circuit: &Circuit, input: &[F]) -> GKRProof<F> {
+		Self::prove_with_outputs(circuit, input).1
+	}
+
+	/// Like [`crate::protocol::GKRProtocol::prove_with_outputs`], in time linear in the size of
+	/// the circuit.
+	pub fn prove_with_outputs<F: PrimeField>(
+		circuit: &Circuit,
+		input: &[F],
+	) -> (Vec<F>, GKRProof<F>) {
+		let evaluation = circuit.evaluate(input);
+		let outputs = evaluation[0].clone();
+		let layers: Vec<Vec<F>> = evaluation
+			.iter()
+			.enumerate()
+			.map(|(i, layer)| pad_layer(layer, circuit.number_of_variables_at_layer(i)))
+			.collect();
+
+		let w_0_mle = w_mle(layers[0].clone());
+		let mut transcript = statement_transcript(circuit, input, &w_0_mle);
+		let n_r: Vec<F> = transcript.sample_n_as_field_element(w_0_mle.number_of_variables);
+		let mut claim = w_0_mle.evaluation(&n_r);
+		let mut weights = eq_mle(&n_r).evaluations;
+
+		let mut sumcheck_proofs = vec![];
+		let mut w_i_b = vec![];
+		let mut w_i_c = vec![];
+		for (layer_index, layer) in circuit.layers.iter().enumerate() {
+			let w_i_mle = w_mle(layers[layer_index + 1].clone());
+			let statement = layer_sumcheck_statement(2 * w_i_mle.number_of_variables, claim);
+			let (sumcheck_proof, challenges) =
+				prove_layer(layer, &weights, &w_i_mle, &statement, &mut transcript);
+			sumcheck_proofs.push(sumcheck_proof);
+
+			let (rand_b, rand_c) = challenges.split_at(challenges.len() / 2);
+			let eval_w_i_b = w_i_mle.evaluation(rand_b);
+			let eval_w_i_c = w_i_mle.evaluation(rand_c);
+			w_i_b.push(eval_w_i_b);
+			w_i_c.push(eval_w_i_c);
+
+			absorb_layer_claims(&mut transcript, &eval_w_i_b, &eval_w_i_c);
+			let alpha = transcript.transform_challenge_to_field::<F>();
+			let beta = transcript.transform_challenge_to_field::<F>();
+			claim = alpha * eval_w_i_b + beta * eval_w_i_c;
+
+			// alpha * eq(r_b, g) + beta * eq(r_c, g) for the gates of the next layer
+			weights = eq_mle(rand_b)
+				.evaluations
+				.iter()
+				.zip(eq_mle(rand_c).evaluations.iter())
+				.map(|(eq_b, eq_c)| alpha * eq_b + beta * eq_c)
+				.collect();
+		}
+
+		(outputs, GKRProof { sumcheck_proofs, w_i_b, w_i_c })
+	}
+}
+
+/// The sum-check of one layer, `weights[g]` being the weight of gate `g` of `layer` and `w_mle`
+/// the padded MLE of the layer it reads.
+fn prove_layer<F: PrimeField>(
+	layer: &CircuitLayer,
+	weights: &[F],
+	w_mle: &MultiLinearPolynomialEvaluationForm<F>,
+	statement: &SumcheckStatement<F>,
+	transcript: &mut Transcript,
+) -> (ComposedSumcheckProof<F>, Vec<F>) {
+	let values = &w_mle.evaluations;
+	let mut round_polys = vec![];
+	let mut challenges = vec![];
+	statement.absorb(transcript);
+
+	// phase one, h_1(b) = A(b) + B(b) * W(b)
+	let mut a = vec![F::zero(); values.len()];
+	let mut b = vec![F::zero(); values.len()];
+	for (gate, weight) in layer.layer.iter().zip(weights.iter()) {
+		let [k_0, k_1, k_2, k_3] = gate.gate_type.coefficients().map(field_from_i64::<F>);
+		let [x, y] = gate.inputs;
+		a[x] += *weight * (k_0 + k_2 * values[y]);
+		b[x] += *weight * (k_1 + k_3 * values[y]);
+	}
+	let phase_one = vec![
+		ComposedMultiLinearPolynomial::new(vec![MultiLinearPolynomialEvaluationForm::new(a)]),
+		ComposedMultiLinearPolynomial::new(vec![
+			MultiLinearPolynomialEvaluationForm::new(b),
+			w_mle.clone(),
+		]),
+	];
+	prove_phase(phase_one, transcript, &mut round_polys, &mut challenges);
+
+	// phase two, h_2(c) = C(c) + D(c) * W(c) with b bound to u
+	let eq_u = eq_mle(&challenges).evaluations;
+	let w_u = w_mle.evaluation(&challenges);
+	let mut c = vec![F::zero(); values.len()];
+	let mut d = vec![F::zero(); values.len()];
+	for (gate, weight) in layer.layer.iter().zip(weights.iter()) {
+		let [k_0, k_1, k_2, k_3] = gate.gate_type.coefficients().map(field_from_i64::<F>);
+		let [x, y] = gate.inputs;
+		let weight = *weight * eq_u[x];
+		c[y] += weight * (k_0 + k_1 * w_u);
+		d[y] += weight * (k_2 + k_3 * w_u);
+	}
+	let phase_two = vec![
+		ComposedMultiLinearPolynomial::new(vec![MultiLinearPolynomialEvaluationForm::new(c)]),
+		ComposedMultiLinearPolynomial::new(vec![
+			MultiLinearPolynomialEvaluationForm::new(d),
+			w_mle.clone(),
+		]),
+	];
+	prove_phase(phase_two, transcript, &mut round_polys, &mut challenges);
+
+	(ComposedSumcheckProof { round_polys, sum: statement.sum }, challenges)
+}
+
+/// Runs the rounds of one phase, absorbing every message as
+/// [`sum_check::multi_composedsumcheck::MultiComposedSumcheckProver::prove_statement`] does.
+fn prove_phase<F: PrimeField>(
+	poly: Vec<ComposedMultiLinearPolynomial<F>>,
+	transcript: &mut Transcript,
+	round_polys: &mut Vec<UnivariatePolynomial<F>>,
+	challenges: &mut Vec<F>,
+) {
+	let mut prover = SumcheckProverState::new(poly);
+	let mut challenge = None;
+	while !prover.is_finished() {
+		let round_poly = prover.next_round(challenge).to_univariate();
+		let message = RoundMessage::from_univariate(&round_poly)
+			.expect("round polynomials of a layer have degree two");
+		let random_r = fiat_shamir_challenge(transcript, &message);
+
+		challenge = Some(random_r);
+		challenges.push(random_r);
+		round_polys.push(round_poly);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		circuit::circuit_template,
+		datastructure::{CustomGate, Gate, GateType},
+		protocol::GKRProtocol,
+	};
+	use ark_ff::MontConfig;
+	use ark_ff::{Fp64, MontBackend};
+
+	#[derive(MontConfig)]
+	#[modulus = "17"]
+	#[generator = "3"]
+	struct FqConfig;
+	type Fq = Fp64<MontBackend<FqConfig, 1>>;
+
+	#[test]
+	fn test_libra_proofs_match_the_dense_prover() {
+		let circuit = circuit_template();
+		let input = vec![Fq::from(3u32), Fq::from(2u32), Fq::from(3u32), Fq::from(1u32)];
+		let (outputs, proof) = LibraProver::prove_with_outputs(&circuit, &input);
+		assert_eq!(outputs, vec![Fq::from(36), Fq::from(6)]);
+		assert_eq!(proof, GKRProtocol::prove(&circuit, &input));
+		assert!(GKRProtocol::verify(&circuit, &input, &outputs, &proof));
+	}
+
+	#[test]
+	fn test_libra_prover_with_uneven_layers_and_rich_gates() {
+		let layer_0 = CircuitLayer::new(vec![
+			Gate::new(GateType::Sub, [0, 1]),
+			Gate::new(GateType::Xor, [2, 3]),
+			Gate::unary(GateType::MulConst(-3), 4),
+		]);
+		let layer_1 = CircuitLayer::new(vec![
+			Gate::unary(GateType::AddConst(5), 0),
+			Gate::new(
+				GateType::Custom(CustomGate { constant: 2, left: 0, right: 1, product: 4 }),
+				[1, 2],
+			),
+			Gate::new(GateType::And, [2, 2]),
+			Gate::unary(GateType::Not, 2),
+			Gate::new(GateType::Mul, [1, 0]),
+		]);
+		let circuit = Circuit::new(vec![layer_0, layer_1]);
+		let input = vec![Fq::from(6u32), Fq::from(3u32), Fq::from(1u32)];
+
+		let (outputs, proof) = LibraProver::prove_with_outputs(&circuit, &input);
+		assert_eq!(outputs, circuit.evaluate(&input)[0]);
+		assert!(GKRProtocol::verify(&circuit, &input, &outputs, &proof));
+
+		let other_input = vec![Fq::from(6u32), Fq::from(3u32), Fq::from(0u32)];
+		assert!(!GKRProtocol::verify(&circuit, &other_input, &outputs, &proof));
+	}
+
+	#[test]
+	fn test_wide_layers_prove_and_verify() {
+		// 2^10 gates per layer, far past what dense wiring predicates fit in
+		let width = 1 << 10;
+		let layer = |shift: usize| {
+			CircuitLayer::new(
+				(0..width)
+					.map(|g| {
+						let gate_type = if g % 2 == 0 { GateType::Add } else { GateType::Mul };
+						Gate::new(gate_type, [g, (g * 7 + shift) % width])
+					})
+					.collect(),
+			)
+		};
+		let circuit = Circuit::new(vec![layer(1), layer(3)]);
+		let input: Vec<Fq> = (0..width as u64).map(Fq::from).collect();
+
+		let (outputs, proof) = LibraProver::prove_with_outputs(&circuit, &input);
+		assert!(GKRProtocol::verify(&circuit, &input, &outputs, &proof));
+
+		let mut wrong_outputs = outputs.clone();
+		wrong_outputs[width - 1] += Fq::from(1u32);
+		assert!(!GKRProtocol::verify(&circuit, &input, &wrong_outputs, &proof));
+	}
+}
@@ -1,43 +1,84 @@
 use crate::{
 	datastructure::{Circuit, GKRProof},
-	utils::{perform_layer_one_prove_sumcheck, perform_layer_one_verify_sumcheck, w_mle},
+	utils::{
+		absorb_layer_claims, evaluate_layer_gates, gate_term_predicates, layer_polynomial,
+		layer_sumcheck_statement, pad_layer, perform_layer_one_prove_sumcheck,
+		perform_layer_one_verify_sumcheck, w_mle,
+	},
 };
 use ark_ff::PrimeField;
-use polynomial::composed::{
-	interface::ComposedMultilinearInterface, multilinear::ComposedMultiLinearPolynomial,
+use polynomial::multilinear::{
+	evaluation_form::MultiLinearPolynomialEvaluationForm,
+	interface::MultiLinearPolynomialEvaluationFormTrait,
 };
-use polynomial::multilinear::interface::MultiLinearPolynomialEvaluationFormTrait;
-use sum_check::multi_composedsumcheck::{
-	ComposedSumcheckProof, MultiComposedSumcheckProver, MultiComposedSumcheckVerifier,
+use sum_check::{
+	multi_composedsumcheck::{
+		ComposedSumcheckProof, MultiComposedSumcheckProver, MultiComposedSumcheckVerifier,
+	},
+	util::convert_field_to_byte,
 };
 use transcript::transcription::Transcript;
+
+pub const GKR_PROTOCOL: &[u8] = b"gkr";
+
 pub struct GKRProtocol;
 
+/// The transcript every layer of a GKR proof runs on, seeded with the public statement: the
+/// circuit, its inputs and the claimed outputs.
+pub(crate) fn statement_transcript<F: PrimeField>(
+	circuit: &Circuit,
+	input: &[F],
+	w_0_mle: &MultiLinearPolynomialEvaluationForm<F>,
+) -> Transcript {
+	let mut transcript = Transcript::new();
+	transcript.append(GKR_PROTOCOL);
+	transcript.append(&circuit.to_bytes());
+	transcript.append(&(input.len() as u64).to_le_bytes());
+	for value in input.iter() {
+		transcript.append(&convert_field_to_byte(value));
+	}
+	transcript.append(&w_0_mle.to_bytes());
+	transcript
+}
+
 impl GKRProtocol {
 	pub fn prove<F: PrimeField>(circuit: &Circuit, input: &[F]) -> GKRProof<F> {
-		let mut transcript = Transcript::new();
+		Self::prove_with_outputs(circuit, input).1
+	}
+
+	/// Proves the evaluation of `circuit` on `input`, returning the values of the output layer
+	/// the verifier checks the proof against, without padding.
+	pub fn prove_with_outputs<F: PrimeField>(
+		circuit: &Circuit,
+		input: &[F],
+	) -> (Vec<F>, GKRProof<F>) {
 		let mut sumcheck_proofs: Vec<ComposedSumcheckProof<F>> = Vec::new();
 		let mut w_i_b: Vec<F> = Vec::new();
 		let mut w_i_c: Vec<F> = Vec::new();
 
-		let circuit_evaluation = circuit.evaluate(input);
-		let mut circuit_evaluation_layer_zero_pad = circuit_evaluation[0].clone();
-		circuit_evaluation_layer_zero_pad.push(F::zero());
+		let evaluation = circuit.evaluate(input);
+		let outputs = evaluation[0].clone();
 
-		let w_0_mle = w_mle(circuit_evaluation_layer_zero_pad);
-		transcript.append(&w_0_mle.to_bytes());
+		// every layer is padded with zeros to the width its wiring predicates index
+		let circuit_evaluation: Vec<Vec<F>> = evaluation
+			.iter()
+			.enumerate()
+			.map(|(i, layer)| pad_layer(layer, circuit.number_of_variables_at_layer(i)))
+			.collect();
+
+		let w_0_mle = w_mle(circuit_evaluation[0].clone());
+		let mut transcript = statement_transcript(circuit, input, &w_0_mle);
 
 		let n_r: Vec<F> = transcript.sample_n_as_field_element(w_0_mle.number_of_variables);
 		let claim: F = w_0_mle.evaluation(&n_r);
 
-		let (add_mle, mul_mle) = circuit.add_i_mul_ext::<F>(0);
+		let predicates = circuit.wiring_predicates::<F>(0);
 		let w_1_mle = w_mle(circuit_evaluation[1].clone());
 
 		// Run sumcheck on layer one
 		let (layer_one_claim, alps, bta, layer_one_rand_b, layer_one_rand_c) =
 			perform_layer_one_prove_sumcheck(
-				&add_mle,
-				&mul_mle,
+				&predicates,
 				&w_1_mle,
 				&n_r,
 				&claim,
@@ -50,47 +91,36 @@ impl GKRProtocol {
 		let mut claim = layer_one_claim;
 		let mut alpha = alps;
 		let mut beta = bta;
-		let last_rand_b = layer_one_rand_b;
-		let last_rand_c = layer_one_rand_c;
+		let mut last_rand_b = layer_one_rand_b;
+		let mut last_rand_c = layer_one_rand_c;
 		// starting the GKR round reductions powered by sumcheck (layer 2 to n-1(excluding the input layer))
 		for layer_index in 2..circuit_evaluation.len() {
-			let (add_mle, mul_mle) = circuit.add_i_mul_ext::<F>(layer_index - 1);
+			let predicates = circuit.wiring_predicates::<F>(layer_index - 1);
 			let number_of_round = last_rand_b.len();
 
-			// add(r_b, b, c) ---> add(b, c)
-			let add_rb_bc = add_mle.partial_evaluations(&last_rand_b, &vec![0; number_of_round]);
-			// mul(r_b, b, c) ---> mul(b, c)
-			let mul_rb_bc = mul_mle.partial_evaluations(&last_rand_b, &vec![0; number_of_round]);
-			// add(r_c, b, c) ---> add(b, c)
-			let add_rc_bc = add_mle.partial_evaluations(&last_rand_c, &vec![0; number_of_round]);
-			// mul(r_c, b, c) ---> mul(b, c)
-			let mul_rc_bc = mul_mle.partial_evaluations(&last_rand_c, &vec![0; number_of_round]);
+			// alpha * term(r_b, b, c) + beta * term(r_c, b, c) for every term of the gates
+			let terms = gate_term_predicates(&predicates)
+				.iter()
+				.map(|term| {
+					let term_rb_bc = term.partial_evaluations(&last_rand_b, &vec![0; number_of_round]);
+					let term_rc_bc = term.partial_evaluations(&last_rand_c, &vec![0; number_of_round]);
+					(term_rb_bc * alpha) + (term_rc_bc * beta)
+				})
+				.collect();
 			let w_i_mle = w_mle(circuit_evaluation[layer_index].clone());
 
 			let wb = w_i_mle.clone();
 			let wc = w_i_mle;
-			// w_i(b) + w_i(c)
-			let wb_add_wc = wb.cartesian_add(&wc);
-			// w_i(b) * w_i(c)
-
-			let wb_mul_wc = wb.cartesian_mul(&wc);
-			// alpha * add(r_b, b, c) + beta * add(r_c, b, c)
-			let add_alpha_beta = (add_rb_bc * alpha) + (add_rc_bc * beta);
-			// alpha * mul(r_b, b, c) + beta * mul(r_c, b, c)
-			let mul_alpha_beta = (mul_rb_bc * alpha) + (mul_rc_bc * beta);
-
-			// alpha * add(r_b, b, c) + beta * add(r_c, b, c)(w_i(b) + w_i(c))
-			let fbc_add = ComposedMultiLinearPolynomial::new(vec![add_alpha_beta, wb_add_wc]);
 
-			// f(b, c) = alpha * add(r_b, b, c) + beta * add(r_c, b, c)(w_i(b) + w_i(c)) + alpha * mul(r_b, b, c) + beta * mul(r_c, b, c)(w_i(b) * w_i(c))
-			let fbc_mul = ComposedMultiLinearPolynomial::new(vec![mul_alpha_beta, wb_mul_wc]);
+			// f(b, c) = sum_g (alpha * g(r_b, b, c) + beta * g(r_c, b, c)) * q_g(w_i(b), w_i(c))
+			let fbc = layer_polynomial(terms, &wb);
 
 			// this prover that the `claim` is the result of the evalution of the previous layer
+			let statement = layer_sumcheck_statement(2 * wb.number_of_variables, claim);
 			let (sumcheck_proof, challenges) =
-				MultiComposedSumcheckProver::prove_partial(&vec![fbc_add, fbc_mul], &claim)
+				MultiComposedSumcheckProver::prove_statement(&fbc, &statement, &mut transcript)
 					.unwrap();
 
-			transcript.append(&sumcheck_proof.to_bytes());
 			sumcheck_proofs.push(sumcheck_proof);
 			// split challenge between rand_b and rand_c
 			let (rand_b, rand_c) = challenges.split_at(challenges.len() / 2);
@@ -99,74 +129,112 @@ impl GKRProtocol {
 			let eval_w_i_c = wc.evaluation(&rand_c.to_vec());
 			w_i_b.push(eval_w_i_b);
 			w_i_c.push(eval_w_i_c);
+			last_rand_b = rand_b.to_vec();
+			last_rand_c = rand_c.to_vec();
 
+			absorb_layer_claims(&mut transcript, &eval_w_i_b, &eval_w_i_c);
 			alpha = transcript.transform_challenge_to_field::<F>();
 			beta = transcript.transform_challenge_to_field::<F>();
 
 			claim = alpha * eval_w_i_b + beta * eval_w_i_c;
 		}
 
-		GKRProof { sumcheck_proofs, w_i_b, w_i_c, w_0_mle }
+		(outputs, GKRProof { sumcheck_proofs, w_i_b, w_i_c })
 	}
 
-	pub fn verify<F: PrimeField>(circuit: &Circuit, input: &[F], proof: &GKRProof<F>) -> bool {
+	/// Checks that `circuit` maps `input` to `claimed_outputs`, the whole output layer without
+	/// padding. The output MLE is built here, the proof does not carry it.
+	pub fn verify<F: PrimeField>(
+		circuit: &Circuit,
+		input: &[F],
+		claimed_outputs: &[F],
+		proof: &GKRProof<F>,
+	) -> bool {
 		// check sumcheckproof length against w_i_b length
-		if proof.sumcheck_proofs.len() != proof.w_i_b.len()
+		if proof.sumcheck_proofs.len() != circuit.layers.len()
+			|| proof.sumcheck_proofs.len() != proof.w_i_b.len()
 			|| proof.sumcheck_proofs.len() != proof.w_i_c.len()
 		{
 			return false;
 		}
+		if claimed_outputs.len() != circuit.layer_width(0) {
+			return false;
+		}
+		// every gate must read a wire of the layer below, the input for the last layer
+		let depth = circuit.layers.len();
+		let out_of_range = circuit.layers.iter().enumerate().any(|(i, layer)| {
+			let width = if i + 1 < depth { circuit.layer_width(i + 1) } else { input.len() };
+			layer.layer.iter().any(|gate| gate.inputs.iter().any(|index| *index >= width))
+		});
+		if out_of_range {
+			return false;
+		}
 
-		let mut transcript = Transcript::new();
-		transcript.append(&proof.w_0_mle.to_bytes());
-
-		let n_r: Vec<F> =
-			transcript.sample_n_as_field_element::<F>(proof.w_0_mle.number_of_variables);
-		let mut claim = proof.w_0_mle.evaluation(&n_r.clone().as_slice());
+		let w_0_mle = w_mle(pad_layer(claimed_outputs, circuit.number_of_variables_at_layer(0)));
+		let mut transcript = statement_transcript(circuit, input, &w_0_mle);
 
-		let mut last_rand_b: Vec<F> = vec![];
-		let mut last_rand_c: Vec<F> = vec![];
-		let mut alpha: F = F::zero();
-		let mut beta: F = F::zero();
+		let n_r: Vec<F> = transcript.sample_n_as_field_element::<F>(w_0_mle.number_of_variables);
+		let mut claim = w_0_mle.evaluation(&n_r.clone().as_slice());
 
 		//layer one verification logic
-		let (add_mle, mul_mle) = circuit.add_i_mul_ext::<F>(0);
-		let (status, layer_one_sum) = perform_layer_one_verify_sumcheck(
-			&add_mle,
-			&mul_mle,
-			&proof.sumcheck_proofs[0],
-			n_r,
-			&claim,
-			&mut transcript,
-			&proof.w_i_b[0],
-			&proof.w_i_c[0],
-		);
+		let (status, layer_one_sum, alps, bta, layer_one_rand_b, layer_one_rand_c) =
+			perform_layer_one_verify_sumcheck(
+				circuit,
+				&proof.sumcheck_proofs[0],
+				n_r,
+				&claim,
+				&mut transcript,
+				&proof.w_i_b[0],
+				&proof.w_i_c[0],
+			);
 
 		if !status {
 			return false;
 		}
 
 		claim = layer_one_sum;
+		let mut alpha = alps;
+		let mut beta = bta;
+		let mut last_rand_b = layer_one_rand_b;
+		let mut last_rand_c = layer_one_rand_c;
 
 		for i in 1..proof.sumcheck_proofs.len() {
 			if claim != proof.sumcheck_proofs[i].sum {
 				return false;
 			}
 
-			transcript.append(&proof.sumcheck_proofs[i].to_bytes());
+			// the sum-check runs over (b, c), one label of layer i + 1 each
+			let number_of_variables = 2 * circuit.number_of_variables_at_layer(i + 1);
+			let statement = layer_sumcheck_statement(number_of_variables, claim);
+			let verify_subclaim = match MultiComposedSumcheckVerifier::verify_statement(
+				&proof.sumcheck_proofs[i],
+				&statement,
+				&mut transcript,
+			) {
+				Ok(sub_claim) => sub_claim,
+				Err(_) => return false,
+			};
+
+			let w_b = proof.w_i_b[i];
+			let w_c = proof.w_i_c[i];
 
-			let verify_subclaim =
-				MultiComposedSumcheckVerifier::verify_partial(&proof.sumcheck_proofs[i]).unwrap();
 			// split challenge between rand_b and rand_c
 			let (rand_b, rand_c) =
 				verify_subclaim.challenges.split_at(&verify_subclaim.challenges.len() / 2);
 
+			// f(b, c) at the challenges, from the gates of layer i weighted at r_b and r_c
+			let layer = &circuit.layers[i];
+			let gates_at_b = evaluate_layer_gates(layer, &last_rand_b, rand_b, rand_c, w_b, w_c);
+			let gates_at_c = evaluate_layer_gates(layer, &last_rand_c, rand_b, rand_c, w_b, w_c);
+			let fbc_eval = alpha * gates_at_b + beta * gates_at_c;
+			if fbc_eval != verify_subclaim.sum {
+				return false;
+			}
+
 			last_rand_b = rand_b.to_vec();
 			last_rand_c = rand_c.to_vec();
 
-			let w_b = proof.w_i_b[i];
-			let w_c = proof.w_i_c[i];
-
+			absorb_layer_claims(&mut transcript, &w_b, &w_c);
 			let alps = transcript.transform_challenge_to_field::<F>();
 			let bta = transcript.transform_challenge_to_field::<F>();
 
@@ -176,7 +244,8 @@ impl GKRProtocol {
 			beta = bta;
 		}
 		// perform verification for the input layer
-		let w_mle_input = w_mle(input.to_vec());
+		let input_variables = circuit.number_of_variables_at_layer(circuit.layers.len());
+		let w_mle_input = w_mle(pad_layer(input, input_variables));
 
 		let w_mle_input_b = w_mle_input.evaluation(&last_rand_b);
 		let w_mle_input_c = w_mle_input.evaluation(&last_rand_c);
@@ -194,9 +263,13 @@ impl GKRProtocol {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::datastructure::{Circuit, CircuitLayer, Gate, GateType};
+	use crate::datastructure::{Circuit, CircuitLayer, CustomGate, Gate, GateType};
+	use crate::utils::evaluate_gates;
 	use ark_ff::MontConfig;
-	use ark_ff::{Fp64, MontBackend};
+	use ark_ff::{Field, Fp64, MontBackend};
+	use polynomial::composed::{
+		interface::ComposedMultilinearInterface, multilinear::ComposedMultiLinearPolynomial,
+	};
 
 	#[derive(MontConfig)]
 	#[modulus = "17"]
@@ -214,9 +287,277 @@ mod tests {
 		let circuit = Circuit::new(vec![layer_0, layer_1]);
 		let input = vec![Fq::from(2u32), Fq::from(3u32), Fq::from(4u32), Fq::from(5u32)];
 
-		let proof = GKRProtocol::prove(&circuit, &input);
-		let verify = GKRProtocol::verify(&circuit, &input, &proof);
+		let (outputs, proof) = GKRProtocol::prove_with_outputs(&circuit, &input);
+		let verify = GKRProtocol::verify(&circuit, &input, &outputs, &proof);
 
 		assert!(verify);
 	}
+
+	#[test]
+	fn test_gate_inputs_out_of_range_are_rejected() {
+		let layer_1 = CircuitLayer::new(vec![
+			Gate::new(GateType::Add, [0, 1]),
+			Gate::new(GateType::Mul, [2, 3]),
+		]);
+		let circuit = Circuit::new(vec![
+			CircuitLayer::new(vec![Gate::new(GateType::Mul, [0, 1])]),
+			layer_1.clone(),
+		]);
+		let input = vec![Fq::from(2u32), Fq::from(3u32), Fq::from(4u32), Fq::from(5u32)];
+		let (outputs, proof) = GKRProtocol::prove_with_outputs(&circuit, &input);
+
+		// a gate reading past the two gates of the layer below, with a layer 0 sum-check that
+		// passes on the verifier's transcript so the wiring of that gate gets evaluated
+		let past_layer =
+			Circuit::new(vec![CircuitLayer::new(vec![Gate::new(GateType::Mul, [0, 2])]), layer_1]);
+		let w_0_mle = w_mle(pad_layer(&outputs, past_layer.number_of_variables_at_layer(0)));
+		let mut transcript = statement_transcript(&past_layer, &input, &w_0_mle);
+		let n_r: Vec<Fq> = transcript.sample_n_as_field_element(w_0_mle.number_of_variables);
+		let claim = w_0_mle.evaluation(&n_r);
+		let any_poly = vec![ComposedMultiLinearPolynomial::new(vec![w_mle(vec![
+			claim,
+			Fq::from(0u32),
+			Fq::from(0u32),
+			Fq::from(0u32),
+		])])];
+		let (layer_0_proof, _) = MultiComposedSumcheckProver::prove_statement(
+			&any_poly,
+			&layer_sumcheck_statement(2, claim),
+			&mut transcript,
+		)
+		.unwrap();
+		let mut forged = proof.clone();
+		forged.sumcheck_proofs[0] = layer_0_proof;
+		assert!(!GKRProtocol::verify(&past_layer, &input, &outputs, &forged));
+
+		// the last layer reading past the end of a shorter input
+		assert!(!GKRProtocol::verify(&circuit, &input[..3], &outputs, &proof));
+	}
+
+	#[test]
+	fn test_gkr_protocol_with_uneven_layers() {
+		// three outputs over five gates over six inputs, no width is a power of two
+		let layer_0 = CircuitLayer::new(vec![
+			Gate::new(GateType::Add, [0, 1]),
+			Gate::new(GateType::Mul, [2, 3]),
+			Gate::new(GateType::Add, [4, 4]),
+		]);
+		let layer_1 = CircuitLayer::new(vec![
+			Gate::new(GateType::Mul, [0, 1]),
+			Gate::new(GateType::Add, [1, 2]),
+			Gate::new(GateType::Mul, [3, 3]),
+			Gate::new(GateType::Add, [4, 5]),
+			Gate::new(GateType::Mul, [5, 0]),
+		]);
+		let layer_2 = CircuitLayer::new(vec![
+			Gate::new(GateType::Add, [0, 2]),
+			Gate::new(GateType::Mul, [1, 2]),
+			Gate::new(GateType::Add, [1, 1]),
+			Gate::new(GateType::Mul, [0, 0]),
+			Gate::new(GateType::Add, [2, 0]),
+			Gate::new(GateType::Mul, [2, 1]),
+		]);
+		let circuit = Circuit::new(vec![layer_0, layer_1, layer_2]);
+		let input = vec![Fq::from(2u32), Fq::from(3u32), Fq::from(4u32)];
+
+		let (outputs, proof) = GKRProtocol::prove_with_outputs(&circuit, &input);
+		assert_eq!(outputs, circuit.evaluate(&input)[0]);
+		assert_eq!(proof.sumcheck_proofs.len(), 3);
+		assert!(GKRProtocol::verify(&circuit, &input, &outputs, &proof));
+
+		let other_input = vec![Fq::from(2u32), Fq::from(3u32), Fq::from(5u32)];
+		assert!(!GKRProtocol::verify(&circuit, &other_input, &outputs, &proof));
+	}
+
+	#[test]
+	fn test_gkr_protocol_with_two_outputs() {
+		let circuit = crate::circuit::circuit_template();
+		let input = vec![Fq::from(3u32), Fq::from(2u32), Fq::from(3u32), Fq::from(1u32)];
+		let (outputs, proof) = GKRProtocol::prove_with_outputs(&circuit, &input);
+		assert_eq!(outputs, vec![Fq::from(36), Fq::from(6)]);
+		assert!(GKRProtocol::verify(&circuit, &input, &outputs, &proof));
+
+		assert!(!GKRProtocol::verify(&circuit, &input, &[Fq::from(36), Fq::from(7)], &proof));
+		assert!(!GKRProtocol::verify(&circuit, &input, &[Fq::from(6), Fq::from(36)], &proof));
+		// the padding is the verifier's, an explicit zero is not a claim about a third output
+		assert!(!GKRProtocol::verify(&circuit, &input, &[outputs[0]], &proof));
+		assert!(!GKRProtocol::verify(
+			&circuit,
+			&input,
+			&[outputs[0], outputs[1], Fq::from(0)],
+			&proof
+		));
+	}
+
+	#[test]
+	fn test_gkr_protocol_with_richer_gates() {
+		let layer_0 = CircuitLayer::new(vec![
+			Gate::new(GateType::Sub, [0, 1]),
+			Gate::new(GateType::Xor, [2, 3]),
+			Gate::unary(GateType::MulConst(-3), 4),
+		]);
+		let layer_1 = CircuitLayer::new(vec![
+			Gate::unary(GateType::AddConst(5), 0),
+			Gate::new(
+				GateType::Custom(CustomGate { constant: 2, left: 0, right: 1, product: 4 }),
+				[1, 1],
+			),
+			Gate::new(GateType::And, [2, 3]),
+			Gate::unary(GateType::Not, 3),
+			Gate::unary(GateType::Neg, 1),
+		]);
+		let layer_2 = CircuitLayer::new(vec![
+			Gate::unary(GateType::Identity, 0),
+			Gate::new(GateType::Mul, [0, 1]),
+			Gate::unary(GateType::Identity, 2),
+			Gate::unary(GateType::Identity, 3),
+		]);
+		let circuit = Circuit::new(vec![layer_0, layer_1, layer_2]);
+		// the last two inputs are bits
+		let input = vec![Fq::from(6u32), Fq::from(3u32), Fq::from(1u32), Fq::from(0u32)];
+
+		let (outputs, proof) = GKRProtocol::prove_with_outputs(&circuit, &input);
+		assert!(GKRProtocol::verify(&circuit, &input, &outputs, &proof));
+
+		// the same wiring with one gate type changed does not accept the proof
+		let mut other = circuit.clone();
+		other.layers[1].layer[2].gate_type = GateType::Xor;
+		assert!(!GKRProtocol::verify(&other, &input, &outputs, &proof));
+		let mut other = circuit.clone();
+		other.layers[0].layer[2].gate_type = GateType::MulConst(3);
+		assert!(!GKRProtocol::verify(&other, &input, &outputs, &proof));
+	}
+
+	#[test]
+	fn test_layer_gates_match_the_wiring_predicates() {
+		let layer_0 = CircuitLayer::new(vec![
+			Gate::new(GateType::Sub, [0, 3]),
+			Gate::new(GateType::Xor, [2, 1]),
+			Gate::unary(GateType::MulConst(-3), 4),
+		]);
+		let layer_1 = CircuitLayer::new(vec![
+			Gate::new(GateType::Mul, [0, 1]),
+			Gate::new(
+				GateType::Custom(CustomGate { constant: 2, left: 0, right: 1, product: 4 }),
+				[1, 2],
+			),
+			Gate::unary(GateType::Not, 2),
+			Gate::new(GateType::Add, [0, 2]),
+			Gate::unary(GateType::AddConst(5), 1),
+		]);
+		let circuit = Circuit::new(vec![layer_0, layer_1]);
+		let predicates = circuit.wiring_predicates::<Fq>(0);
+
+		let r = [Fq::from(3u32), Fq::from(11u32)];
+		let r_b = [Fq::from(5u32), Fq::from(2u32), Fq::from(13u32)];
+		let r_c = [Fq::from(7u32), Fq::from(16u32), Fq::from(9u32)];
+		let (w_b, w_c) = (Fq::from(4u32), Fq::from(10u32));
+		let point = [r.as_slice(), &r_b, &r_c].concat();
+		assert_eq!(
+			evaluate_layer_gates(&circuit.layers[0], &r, &r_b, &r_c, w_b, w_c),
+			evaluate_gates(&predicates, &point, w_b, w_c)
+		);
+	}
+
+	#[test]
+	fn test_gkr_protocol_with_single_layer() {
+		let layer_0 = CircuitLayer::new(vec![
+			Gate::new(GateType::Sub, [0, 1]),
+			Gate::unary(GateType::AddConst(7), 1),
+		]);
+		let circuit = Circuit::new(vec![layer_0]);
+		let input = vec![Fq::from(2u32), Fq::from(9u32)];
+
+		let (outputs, proof) = GKRProtocol::prove_with_outputs(&circuit, &input);
+		assert_eq!(outputs, vec![-Fq::from(7u32), Fq::from(16u32)]);
+		assert!(GKRProtocol::verify(&circuit, &input, &outputs, &proof));
+		let other_input = [Fq::from(2u32), Fq::from(8u32)];
+		assert!(!GKRProtocol::verify(&circuit, &other_input, &outputs, &proof));
+	}
+
+	/// A proof that `circuit`, a single `Add` gate, outputs `forged_output`, against the transcript
+	/// schedule the verifier used to follow: a fresh transcript for every sum-check, and `alpha`,
+	/// `beta` drawn before `w(r_b)`, `w(r_c)` were absorbed. Knowing `alpha` and `beta` in advance
+	/// the prover solves for the two claims that pass both the layer check and the input check.
+	fn forge_add_proof(circuit: &Circuit, input: &[Fq], forged_output: Fq) -> GKRProof<Fq> {
+		let w_0_mle = w_mle(vec![forged_output, Fq::from(0)]);
+		let mut transcript = Transcript::new();
+		transcript.append(&w_0_mle.to_bytes());
+		let n_r: Vec<Fq> = transcript.sample_n_as_field_element(1);
+		let claim = w_0_mle.evaluation(&n_r);
+
+		// an honest sum-check over inputs that do add up to the forged output
+		let predicates = circuit.wiring_predicates::<Fq>(0);
+		let fake_input = w_mle(vec![forged_output - input[1], input[1]]);
+		let terms = gate_term_predicates(&predicates)
+			.iter()
+			.map(|term| term.partial_evaluations(&n_r, &vec![0; n_r.len()]))
+			.collect();
+		let fbc = layer_polynomial(terms, &fake_input);
+		let (sumcheck_proof, challenges) =
+			MultiComposedSumcheckProver::prove_internal(&fbc, &claim, &mut Transcript::new())
+				.unwrap();
+		transcript.append(&sumcheck_proof.to_bytes());
+		let alpha: Fq = transcript.transform_challenge_to_field();
+		let beta: Fq = transcript.transform_challenge_to_field();
+
+		// add(r, r_b, r_c) * (w_b + w_c) = sum and alpha * w_b + beta * w_c = target
+		let point = [n_r.as_slice(), &challenges].concat();
+		let add = evaluate_gates(&predicates, &point, Fq::from(1), Fq::from(0));
+		let sum = evaluate_gates(
+			&predicates,
+			&point,
+			fake_input.evaluation(&challenges[..1]),
+			fake_input.evaluation(&challenges[1..]),
+		);
+		let real_input = w_mle(input.to_vec());
+		let target = alpha * real_input.evaluation(&challenges[..1])
+			+ beta * real_input.evaluation(&challenges[1..]);
+		let w_b_plus_w_c = sum * add.inverse().unwrap();
+		let w_b = (target - beta * w_b_plus_w_c) * (alpha - beta).inverse().unwrap();
+		let w_c = w_b_plus_w_c - w_b;
+
+		GKRProof { sumcheck_proofs: vec![sumcheck_proof], w_i_b: vec![w_b], w_i_c: vec![w_c] }
+	}
+
+	#[test]
+	fn test_forged_output_is_rejected() {
+		let circuit = Circuit::new(vec![CircuitLayer::new(vec![Gate::new(GateType::Add, [0, 1])])]);
+		let input = vec![Fq::from(2u32), Fq::from(9u32)];
+		let forged_output = Fq::from(5u32);
+		let forged = forge_add_proof(&circuit, &input, forged_output);
+
+		// replay the checks of the per-layer transcripts, all of them pass for the wrong output
+		let w_0_mle = w_mle(vec![forged_output, Fq::from(0)]);
+		let mut transcript = Transcript::new();
+		transcript.append(&w_0_mle.to_bytes());
+		let n_r: Vec<Fq> = transcript.sample_n_as_field_element(1);
+		assert_eq!(w_0_mle.evaluation(&n_r), forged.sumcheck_proofs[0].sum);
+		transcript.append(&forged.sumcheck_proofs[0].to_bytes());
+		let sub_claim = MultiComposedSumcheckVerifier::verify_internal(
+			&forged.sumcheck_proofs[0],
+			2,
+			2,
+			&mut Transcript::new(),
+		)
+		.unwrap();
+		let point = [n_r.as_slice(), &sub_claim.challenges].concat();
+		let predicates = circuit.wiring_predicates::<Fq>(0);
+		assert_eq!(
+			evaluate_gates(&predicates, &point, forged.w_i_b[0], forged.w_i_c[0]),
+			sub_claim.sum
+		);
+		let alpha: Fq = transcript.transform_challenge_to_field();
+		let beta: Fq = transcript.transform_challenge_to_field();
+		let input_mle = w_mle(input.clone());
+		assert_eq!(
+			alpha * input_mle.evaluation(&sub_claim.challenges[..1])
+				+ beta * input_mle.evaluation(&sub_claim.challenges[1..]),
+			alpha * forged.w_i_b[0] + beta * forged.w_i_c[0]
+		);
+
+		assert!(!GKRProtocol::verify(&circuit, &input, &[forged_output], &forged));
+		let proof = GKRProtocol::prove(&circuit, &input);
+		assert!(GKRProtocol::verify(&circuit, &input, &[Fq::from(11u32)], &proof));
+	}
 }
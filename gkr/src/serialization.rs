@@ -0,0 +1,113 @@
+//! Binary encoding of GKR proofs.
+//!
+//! | bytes | content                                                        |
+//! |-------|----------------------------------------------------------------|
+//! | 1     | format version, currently `2`                                  |
+//! | 4     | number of layers `L`, little-endian `u32`                      |
+//! |       | `L` times: `u32` length and the encoded layer sum-check proof, |
+//! |       | then `w_i(r_b)` and `w_i(r_c)`                                 |
+//!
+//! Field elements and sum-check proofs use the encoding of
+//! [`sum_check::serialization`]. Decoding rejects the same malformed inputs.
+
+use crate::datastructure::GKRProof;
+use ark_ff::PrimeField;
+use sum_check::{
+	multi_composedsumcheck::ComposedSumcheckProof,
+	serialization::{ProofEncoding, ProofReader, ProofWriter},
+};
+
+pub const GKR_PROOF_FORMAT_VERSION: u8 = 2;
+
+impl<F: PrimeField> ProofEncoding for GKRProof<F> {
+	fn encode(&self) -> Result<Vec<u8>, &'static str> {
+		if self.sumcheck_proofs.len() != self.w_i_b.len()
+			|| self.sumcheck_proofs.len() != self.w_i_c.len()
+		{
+			return Err("Proof has a different number of sum-checks and layer claims");
+		}
+
+		let mut writer = ProofWriter::default();
+		writer.put_u8(GKR_PROOF_FORMAT_VERSION);
+		writer.put_u32(self.sumcheck_proofs.len())?;
+
+		for ((sumcheck_proof, w_b), w_c) in
+			self.sumcheck_proofs.iter().zip(self.w_i_b.iter()).zip(self.w_i_c.iter())
+		{
+			let encoded = sumcheck_proof.encode()?;
+			writer.put_u32(encoded.len())?;
+			writer.put_bytes(&encoded);
+			writer.put_field(w_b);
+			writer.put_field(w_c);
+		}
+		Ok(writer.into_bytes())
+	}
+
+	fn decode(bytes: &[u8], max_size: usize) -> Result<Self, &'static str> {
+		let mut reader = ProofReader::with_size_limit(bytes, max_size)?;
+		if reader.get_u8()? != GKR_PROOF_FORMAT_VERSION {
+			return Err("Unsupported proof format version");
+		}
+
+		let number_of_layers = reader.get_u32()?;
+		let mut sumcheck_proofs = vec![];
+		let mut w_i_b = vec![];
+		let mut w_i_c = vec![];
+		for _ in 0..number_of_layers {
+			let length = reader.get_u32()?;
+			let encoded = reader.take(length)?;
+			sumcheck_proofs.push(ComposedSumcheckProof::decode(encoded, max_size)?);
+			w_i_b.push(reader.get_field()?);
+			w_i_c.push(reader.get_field()?);
+		}
+
+		reader.finish()?;
+		Ok(GKRProof { sumcheck_proofs, w_i_b, w_i_c })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{circuit::circuit_template, protocol::GKRProtocol};
+	use ark_ff::MontConfig;
+	use ark_ff::{Fp64, MontBackend};
+	use sum_check::serialization::DEFAULT_MAX_PROOF_SIZE;
+
+	#[derive(MontConfig)]
+	#[modulus = "17"]
+	#[generator = "3"]
+	struct FqConfig;
+	type Fq = Fp64<MontBackend<FqConfig, 1>>;
+
+	#[test]
+	fn test_gkr_proof_round_trip() {
+		let circuit = circuit_template();
+		let input = vec![Fq::from(3u32), Fq::from(2u32), Fq::from(3u32), Fq::from(1u32)];
+		let (outputs, proof) = GKRProtocol::prove_with_outputs(&circuit, &input);
+
+		let bytes = proof.encode().unwrap();
+		let decoded = GKRProof::<Fq>::decode(&bytes, DEFAULT_MAX_PROOF_SIZE).unwrap();
+		assert_eq!(decoded, proof);
+		assert!(GKRProtocol::verify(&circuit, &input, &outputs, &decoded));
+
+		assert!(GKRProof::<Fq>::decode(&bytes[..bytes.len() - 1], DEFAULT_MAX_PROOF_SIZE).is_err());
+		let mut trailing = bytes.clone();
+		trailing.push(0);
+		assert!(GKRProof::<Fq>::decode(&trailing, DEFAULT_MAX_PROOF_SIZE).is_err());
+		assert!(GKRProof::<Fq>::decode(&bytes, bytes.len() - 1).is_err());
+
+		let mut huge = bytes.clone();
+		huge[1..5].copy_from_slice(&u32::MAX.to_le_bytes());
+		assert_eq!(
+			GKRProof::<Fq>::decode(&huge, DEFAULT_MAX_PROOF_SIZE),
+			Err("Unexpected end of proof")
+		);
+		let mut old_version = bytes;
+		old_version[0] = 1;
+		assert_eq!(
+			GKRProof::<Fq>::decode(&old_version, DEFAULT_MAX_PROOF_SIZE),
+			Err("Unsupported proof format version")
+		);
+	}
+}
@@ -1,3 +1,7 @@
+use crate::{
+	circuit::field_from_i64,
+	datastructure::{Circuit, CircuitLayer, GateType},
+};
 use ark_ff::PrimeField;
 use polynomial::{
 	composed::{
@@ -5,26 +9,28 @@ use polynomial::{
 	},
 	multilinear::{
 		evaluation_form::MultiLinearPolynomialEvaluationForm,
-		interface::MultiLinearPolynomialEvaluationFormTrait,
+		interface::MultiLinearPolynomialEvaluationFormTrait, utils::eq_mle,
 	},
 };
 use sum_check::multi_composedsumcheck::MultiComposedSumcheckVerifier;
 use sum_check::multi_composedsumcheck::{ComposedSumcheckProof, MultiComposedSumcheckProver};
+use sum_check::statement::{SumcheckStatement, MULTI_COMPOSED_SUMCHECK_PROTOCOL};
+use sum_check::util::convert_field_to_byte;
 use transcript::transcription::Transcript;
-//look into this.
-pub fn label_to_binary_to_decimal(a: usize, b: usize, c: usize) -> usize {
-	let a_shifted = a << 4;
-	let b_shifted = b << 2;
+/// Index of the wiring predicate entry for gate `a` reading `b` and `c`, each input label taking
+/// `input_variables` bits.
+pub fn label_to_binary_to_decimal(a: usize, b: usize, c: usize, input_variables: usize) -> usize {
+	let a_shifted = a << (2 * input_variables);
+	let b_shifted = b << input_variables;
 	a_shifted | b_shifted | c
 }
 
-pub fn size_of_number_of_variable_at_each_layer(layer_index: usize) -> usize {
-	if layer_index == 0 {
-		return 1 << 3;
-	}
-	let layer_index_plus_one = layer_index + 1;
-	let number_of_variable = layer_index + (2 * layer_index_plus_one);
-	1 << number_of_variable
+/// The values of a layer padded with zeros to `2^number_of_variables` entries. Values past that
+/// are dropped, no gate reads them.
+pub fn pad_layer<F: PrimeField>(values: &[F], number_of_variables: usize) -> Vec<F> {
+	let mut padded = values.to_vec();
+	padded.resize(1 << number_of_variables, F::zero());
+	padded
 }
 pub fn gen_w_mle<F: PrimeField>(
 	evals: &[Vec<F>],
@@ -37,9 +43,112 @@ pub fn gen_w_mle<F: PrimeField>(
 	MultiLinearPolynomialEvaluationForm::interpolate(&evals[layer_index])
 }
 
+/// The wiring predicates of a layer folded into one MLE per term of `q(x, y)`: the sums of
+/// `coefficient * gate_type_i(a, b, c)` for the constant, `x`, `y` and `xy` terms.
+pub fn gate_term_predicates<F: PrimeField>(
+	predicates: &[(GateType, MultiLinearPolynomialEvaluationForm<F>)],
+) -> Vec<MultiLinearPolynomialEvaluationForm<F>> {
+	let number_of_variables = predicates[0].1.number_of_variables;
+	let mut terms = vec![MultiLinearPolynomialEvaluationForm::zero(number_of_variables); 4];
+	for (gate_type, predicate) in predicates.iter() {
+		for (term, coefficient) in terms.iter_mut().zip(gate_type.coefficients()) {
+			if coefficient != 0 {
+				*term += predicate.clone() * field_from_i64::<F>(coefficient);
+			}
+		}
+	}
+	terms
+}
+
+/// The layer sum-check polynomial `f(b, c) = sum_t term_t(b, c) * m_t(w(b), w(c))` for the
+/// monomials `m = (1, x, y, xy)`, given the term predicates with the output label fixed.
+pub fn layer_polynomial<F: PrimeField>(
+	terms: Vec<MultiLinearPolynomialEvaluationForm<F>>,
+	w_mle: &MultiLinearPolynomialEvaluationForm<F>,
+) -> Vec<ComposedMultiLinearPolynomial<F>> {
+	let zero = MultiLinearPolynomialEvaluationForm::zero(w_mle.number_of_variables);
+	// w(b) and w(c) as polynomials in (b, c)
+	let wb = w_mle.cartesian_add(&zero);
+	let wc = zero.cartesian_add(w_mle);
+	let wb_mul_wc = w_mle.cartesian_mul(w_mle);
+
+	let polys: Vec<ComposedMultiLinearPolynomial<F>> = terms
+		.into_iter()
+		.zip([None, Some(wb), Some(wc), Some(wb_mul_wc)])
+		.filter(|(term, _)| !term.is_zero())
+		.map(|(term, monomial)| match monomial {
+			None => ComposedMultiLinearPolynomial::new(vec![term]),
+			Some(monomial) => ComposedMultiLinearPolynomial::new(vec![term, monomial]),
+		})
+		.collect();
+
+	if polys.is_empty() {
+		// every gate of the layer is the zero gate
+		let number_of_variables = 2 * w_mle.number_of_variables;
+		return vec![ComposedMultiLinearPolynomial::new(vec![
+			MultiLinearPolynomialEvaluationForm::zero(number_of_variables),
+		])];
+	}
+	polys
+}
+
+/// `sum_g gate_type_i(point) * q_g(w_b, w_c)` over the wiring predicates of a layer.
+pub fn evaluate_gates<F: PrimeField>(
+	predicates: &[(GateType, MultiLinearPolynomialEvaluationForm<F>)],
+	point: &[F],
+	w_b: F,
+	w_c: F,
+) -> F {
+	predicates
+		.iter()
+		.map(|(gate_type, predicate)| predicate.evaluation(point) * gate_type.evaluate(w_b, w_c))
+		.sum()
+}
+
+/// `sum_g eq(r, g) * eq(r_b, in_0(g)) * eq(r_c, in_1(g)) * q_g(w_b, w_c)` over the gates of
+/// `layer`, which is [`evaluate_gates`] at `(r, r_b, r_c)` without the `2^(a + 2b)` wiring
+/// predicates. The eq tables have one entry per label of a layer, so the cost is linear in the
+/// gates and the widths of the two layers.
+pub fn evaluate_layer_gates<F: PrimeField>(
+	layer: &CircuitLayer,
+	r: &[F],
+	r_b: &[F],
+	r_c: &[F],
+	w_b: F,
+	w_c: F,
+) -> F {
+	let eq_r = eq_mle(r).evaluations;
+	let eq_r_b = eq_mle(r_b).evaluations;
+	let eq_r_c = eq_mle(r_c).evaluations;
+	layer
+		.layer
+		.iter()
+		.enumerate()
+		.map(|(gate_index, gate)| {
+			let [b, c] = gate.inputs;
+			eq_r[gate_index] * eq_r_b[b] * eq_r_c[c] * gate.gate_type.evaluate(w_b, w_c)
+		})
+		.sum()
+}
+
+/// The statement of the sum-check reducing a claim about layer `i` to layer `i + 1`. The layer
+/// polynomial `f(b, c) = sum_g g(r, b, c) * q_g(w(b), w(c))` has degree two in every variable.
+pub fn layer_sumcheck_statement<F: PrimeField>(
+	number_of_variables: usize,
+	sum: F,
+) -> SumcheckStatement<F> {
+	SumcheckStatement::new(MULTI_COMPOSED_SUMCHECK_PROTOCOL, number_of_variables, 2, sum)
+}
+
+/// Absorbs the prover's claims `w(r_b)` and `w(r_c)`, so the challenges combining them are drawn
+/// after the prover is bound to both.
+pub fn absorb_layer_claims<F: PrimeField>(transcript: &mut Transcript, w_b: &F, w_c: &F) {
+	transcript.append(&convert_field_to_byte(w_b));
+	transcript.append(&convert_field_to_byte(w_c));
+}
+
 pub fn perform_layer_one_prove_sumcheck<F: PrimeField>(
-	add_mle: &MultiLinearPolynomialEvaluationForm<F>,
-	mul_mle: &MultiLinearPolynomialEvaluationForm<F>,
+	predicates: &[(GateType, MultiLinearPolynomialEvaluationForm<F>)],
 	w_mle: &MultiLinearPolynomialEvaluationForm<F>,
 	n_r: &Vec<F>,
 	sum: &F,
@@ -48,21 +157,20 @@ pub fn perform_layer_one_prove_sumcheck<F: PrimeField>(
 	w_i_b: &mut Vec<F>,
 	w_i_c: &mut Vec<F>,
 ) -> (F, F, F, Vec<F>, Vec<F>) {
-	let add_rbc = add_mle.partial_evaluations(&n_r, &vec![0; n_r.len()]);
-	let mul_rbc = mul_mle.partial_evaluations(&n_r, &vec![0; n_r.len()]);
+	// term(r, b, c) ---> term(b, c)
+	let terms_rbc = gate_term_predicates(predicates)
+		.iter()
+		.map(|term| term.partial_evaluations(&n_r, &vec![0; n_r.len()]))
+		.collect();
 
 	let wb = w_mle.clone();
 	let wc = w_mle;
 
-	let wb_add_wc = wb.cartesian_add(&wc);
-	let wb_mul_wc = wb.cartesian_mul(&wc);
-
-	let add_fbc = ComposedMultiLinearPolynomial::new(vec![add_rbc, wb_add_wc]);
-	let mul_fbc = ComposedMultiLinearPolynomial::new(vec![mul_rbc, wb_mul_wc]);
+	let fbc = layer_polynomial(terms_rbc, w_mle);
 
+	let statement = layer_sumcheck_statement(2 * w_mle.number_of_variables, *sum);
 	let (sumcheck_proof, challenges) =
-		MultiComposedSumcheckProver::prove_partial(&vec![add_fbc, mul_fbc], &sum).unwrap();
-	transcript.append(&sumcheck_proof.to_bytes());
+		MultiComposedSumcheckProver::prove_statement(&fbc, &statement, transcript).unwrap();
 	sumcheck_proofs.push(sumcheck_proof);
 
 	let (rand_b, rand_c) = challenges.split_at(&challenges.len() / 2);
@@ -72,6 +180,7 @@ pub fn perform_layer_one_prove_sumcheck<F: PrimeField>(
 	w_i_b.push(eval_w_i_b);
 	w_i_c.push(eval_w_i_c);
 
+	absorb_layer_claims(transcript, &eval_w_i_b, &eval_w_i_c);
 	let alpha = transcript.transform_challenge_to_field::<F>();
 	let beta = transcript.transform_challenge_to_field::<F>();
 
@@ -81,45 +190,45 @@ pub fn perform_layer_one_prove_sumcheck<F: PrimeField>(
 	(claimed_sum, alpha, beta, rand_b.to_vec(), rand_c.to_vec())
 }
 
+/// Checks the sum-check of the output layer of `circuit`.
 pub fn perform_layer_one_verify_sumcheck<F: PrimeField>(
-	add_mle: &MultiLinearPolynomialEvaluationForm<F>,
-	mul_mle: &MultiLinearPolynomialEvaluationForm<F>,
+	circuit: &Circuit,
 	proof: &ComposedSumcheckProof<F>,
 	n_r: Vec<F>,
 	layer_one_expected_sum: &F,
 	transcript: &mut Transcript,
 	w_b: &F,
 	w_c: &F,
-) -> (bool, F) {
+) -> (bool, F, F, F, Vec<F>, Vec<F>) {
+	let rejected = (false, F::zero(), F::zero(), F::zero(), vec![], vec![]);
 	if *layer_one_expected_sum != proof.sum {
-		return (false, F::zero());
+		return rejected;
 	}
 
-	transcript.append(&proof.to_bytes());
-
-	let verify_subclaim = MultiComposedSumcheckVerifier::verify_partial(proof).unwrap();
-
-	let mut r_b_c = n_r;
-	r_b_c.extend_from_slice(&verify_subclaim.challenges);
-
-	let add_b_c = add_mle.evaluation(&r_b_c);
-	let mul_b_c = mul_mle.evaluation(&r_b_c);
+	let input_variables = circuit.number_of_variables_at_layer(1);
+	let statement = layer_sumcheck_statement(2 * input_variables, proof.sum);
+	let verify_subclaim =
+		match MultiComposedSumcheckVerifier::verify_statement(proof, &statement, transcript) {
+			Ok(sub_claim) => sub_claim,
+			Err(_) => return rejected,
+		};
 
-	let fbc_add = add_b_c * (*w_b + *w_c);
-	let fbc_mul = mul_b_c * (*w_b * *w_c);
+	let (rand_b, rand_c) =
+		verify_subclaim.challenges.split_at(verify_subclaim.challenges.len() / 2);
 
-	let fbc_eval = fbc_add + fbc_mul;
+	let fbc_eval = evaluate_layer_gates(&circuit.layers[0], &n_r, rand_b, rand_c, *w_b, *w_c);
 
 	if fbc_eval != verify_subclaim.sum {
-		return (false, F::zero());
+		return rejected;
 	}
 
+	absorb_layer_claims(transcript, w_b, w_c);
 	let alpha = transcript.transform_challenge_to_field::<F>();
 	let beta = transcript.transform_challenge_to_field::<F>();
 
 	let new_claim: F = alpha * w_b + beta * w_c;
 
-	(true, new_claim)
+	(true, new_claim, alpha, beta, rand_b.to_vec(), rand_c.to_vec())
 }
 
 pub fn w_mle<F: PrimeField>(layer_eval: Vec<F>) -> MultiLinearPolynomialEvaluationForm<F> {
@@ -130,7 +239,8 @@ mod tests {
 	use super::*;
 	#[test]
 	fn test_label_binary_and_to_decimal() {
-		assert_eq!(label_to_binary_to_decimal(0, 0, 1), 1);
-		assert_eq!(label_to_binary_to_decimal(1, 2, 3), 27);
+		assert_eq!(label_to_binary_to_decimal(0, 0, 1, 2), 1);
+		assert_eq!(label_to_binary_to_decimal(1, 2, 3, 2), 27);
+		assert_eq!(label_to_binary_to_decimal(1, 0, 1, 1), 5);
 	}
 }
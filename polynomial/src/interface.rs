@@ -14,4 +14,17 @@ pub trait MLETrait<F: PrimeField> {
     fn new(evaluations: Vec<F>) -> Self;
     fn partial_evaluation(&self, eval_point: F, variable_index: usize) -> Self;
     fn evaluation(&self, evaluation_points: &[F]) -> F;
-}
\ No newline at end of file
+}
+
+/// A polynomial in `number_of_variables` variables that is only accessed through its evaluations.
+pub trait MultivariatePolynomial<F: PrimeField> {
+	fn number_of_variables(&self) -> usize;
+	/// An upper bound on the degree of the polynomial in `variable`.
+	fn degree(&self, variable: usize) -> usize;
+	fn evaluate(&self, point: &[F]) -> F;
+
+	/// The largest degree in any single variable.
+	fn max_degree(&self) -> usize {
+		(0..self.number_of_variables()).map(|variable| self.degree(variable)).max().unwrap_or(0)
+	}
+}
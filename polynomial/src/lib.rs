@@ -1,6 +1,7 @@
 pub mod univariate_polynomial;
 pub mod multilinear;
 pub mod composed;
+pub mod multivariate;
 pub use multilinear::coefficient_form::{MultilinearMonomial, MultilinearPolynomial};
 pub mod interface;
 pub mod util;
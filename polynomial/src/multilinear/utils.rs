@@ -1,3 +1,6 @@
+use crate::multilinear::evaluation_form::MultiLinearPolynomialEvaluationForm;
+use ark_ff::PrimeField;
+
 
 pub fn pick_pairs_with_index(
     num_of_evaluations: usize,
@@ -44,4 +47,62 @@ pub fn compute_number_of_variables(n: u128) -> (u128, u128) {
     }
 
     (log_base_2 as u128, n_power_2)
-}
\ No newline at end of file
+}
+
+/// Builds the evaluations of `eq(r, x)` over the boolean hypercube, where `eq(r, x) = 1` iff
+/// `x == r` on boolean inputs. The first element of `r` is bound to the most significant bit of the index,
+/// matching the variable ordering used by `partial_evaluation`.
+pub fn eq_mle<F: PrimeField>(r: &[F]) -> MultiLinearPolynomialEvaluationForm<F> {
+    let mut evaluations = vec![F::one()];
+
+    for r_i in r {
+        let mut next = Vec::with_capacity(evaluations.len() * 2);
+        for eval in evaluations.iter() {
+            let hi = *eval * r_i;
+            next.push(*eval - hi);
+            next.push(hi);
+        }
+        evaluations = next;
+    }
+
+    MultiLinearPolynomialEvaluationForm { number_of_variables: r.len(), evaluations }
+}
+
+/// Evaluates `eq(x, y) = prod_i (x_i * y_i + (1 - x_i) * (1 - y_i))` at two points of equal length.
+pub fn eq_evaluation<F: PrimeField>(x: &[F], y: &[F]) -> F {
+    assert_eq!(x.len(), y.len(), "eq points must have the same length");
+
+    x.iter()
+        .zip(y.iter())
+        .fold(F::one(), |acc, (x_i, y_i)| acc * (*x_i * y_i + (F::one() - x_i) * (F::one() - y_i)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multilinear::interface::MultiLinearPolynomialEvaluationFormTrait;
+    use ark_ff::MontConfig;
+    use ark_ff::{Fp64, MontBackend};
+
+    #[derive(MontConfig)]
+    #[modulus = "17"]
+    #[generator = "3"]
+    struct FqConfig;
+    type Fq = Fp64<MontBackend<FqConfig, 1>>;
+
+    #[test]
+    fn test_eq_mle_on_boolean_point() {
+        let eq = eq_mle(&[Fq::from(1), Fq::from(0)]);
+        assert_eq!(eq.evaluations, vec![Fq::from(0), Fq::from(0), Fq::from(1), Fq::from(0)]);
+    }
+
+    #[test]
+    fn test_eq_mle_matches_eq_evaluation() {
+        let r = vec![Fq::from(3), Fq::from(5), Fq::from(7)];
+        let x = vec![Fq::from(2), Fq::from(11), Fq::from(4)];
+        let eq = eq_mle(&r);
+
+        assert_eq!(eq.evaluation(&x), eq_evaluation(&r, &x));
+        assert_eq!(eq.sum_over_the_boolean_hypercube(), Fq::from(1));
+    }
+}
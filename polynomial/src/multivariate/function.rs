@@ -0,0 +1,39 @@
+use crate::interface::MultivariatePolynomial;
+use ark_ff::PrimeField;
+use std::marker::PhantomData;
+
+/// A multivariate polynomial given by a closure that evaluates it, together with the bound on its
+/// degree in each variable. The closure must agree with a polynomial within those bounds at every
+/// point, not just on the hypercube.
+pub struct MultivariateFunction<F: PrimeField, G: Fn(&[F]) -> F> {
+	degrees: Vec<usize>,
+	function: G,
+	_field: PhantomData<F>,
+}
+
+impl<F: PrimeField, G: Fn(&[F]) -> F> MultivariateFunction<F, G> {
+	/// Degree at most `degree` in each of the `number_of_variables` variables.
+	pub fn new(number_of_variables: usize, degree: usize, function: G) -> Self {
+		Self::with_degrees(vec![degree; number_of_variables], function)
+	}
+
+	/// `degrees[i]` bounds the degree in the `i`-th variable.
+	pub fn with_degrees(degrees: Vec<usize>, function: G) -> Self {
+		Self { degrees, function, _field: PhantomData }
+	}
+}
+
+impl<F: PrimeField, G: Fn(&[F]) -> F> MultivariatePolynomial<F> for MultivariateFunction<F, G> {
+	fn number_of_variables(&self) -> usize {
+		self.degrees.len()
+	}
+
+	fn degree(&self, variable: usize) -> usize {
+		self.degrees[variable]
+	}
+
+	fn evaluate(&self, point: &[F]) -> F {
+		assert_eq!(point.len(), self.degrees.len(), "Point has the wrong number of coordinates");
+		(self.function)(point)
+	}
+}
@@ -0,0 +1,356 @@
+use crate::composed::multilinear::ComposedMultiLinearPolynomial;
+use crate::interface::MultivariatePolynomial;
+use crate::multilinear::evaluation_form::MultiLinearPolynomialEvaluationForm;
+use ark_ff::{BigInteger, PrimeField};
+use std::{
+	fmt::{Display, Formatter, Result},
+	ops::{Add, Mul},
+};
+
+/// `coefficient * x_0^exponents[0] * x_1^exponents[1] * ...`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SparseMonomial<F: PrimeField> {
+	pub coefficient: F,
+	pub exponents: Vec<usize>,
+}
+
+/// A multivariate polynomial stored as its non-zero monomials, sorted by exponent vector, so
+/// every variable can have any degree. Two polynomials are equal exactly when their terms are.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SparseMultivariatePolynomial<F: PrimeField> {
+	pub number_of_variables: usize,
+	pub terms: Vec<SparseMonomial<F>>,
+}
+
+impl<F: PrimeField> SparseMultivariatePolynomial<F> {
+	/// Builds the polynomial from `(coefficient, exponents)` pairs, merging like terms.
+	pub fn new(number_of_variables: usize, terms: Vec<(F, Vec<usize>)>) -> Self {
+		let terms = terms
+			.into_iter()
+			.map(|(coefficient, exponents)| {
+				assert_eq!(
+					exponents.len(),
+					number_of_variables,
+					"Every monomial needs one exponent per variable"
+				);
+				SparseMonomial { coefficient, exponents }
+			})
+			.collect();
+		Self::from_terms(number_of_variables, terms)
+	}
+
+	pub fn zero(number_of_variables: usize) -> Self {
+		Self { number_of_variables, terms: vec![] }
+	}
+
+	pub fn constant(number_of_variables: usize, value: F) -> Self {
+		Self::new(number_of_variables, vec![(value, vec![0; number_of_variables])])
+	}
+
+	pub fn is_zero(&self) -> bool {
+		self.terms.is_empty()
+	}
+
+	fn from_terms(number_of_variables: usize, mut terms: Vec<SparseMonomial<F>>) -> Self {
+		terms.sort_by(|a, b| a.exponents.cmp(&b.exponents));
+
+		let mut merged: Vec<SparseMonomial<F>> = Vec::with_capacity(terms.len());
+		for term in terms {
+			match merged.last_mut() {
+				Some(last) if last.exponents == term.exponents => {
+					last.coefficient += term.coefficient
+				},
+				_ => merged.push(term),
+			}
+		}
+		merged.retain(|term| !term.coefficient.is_zero());
+
+		Self { number_of_variables, terms: merged }
+	}
+
+	/// The multilinear polynomial with the same evaluations on the boolean hypercube. The first
+	/// variable is the most significant bit of the evaluation index.
+	pub fn from_mle(mle: &MultiLinearPolynomialEvaluationForm<F>) -> Self {
+		let n = mle.number_of_variables;
+
+		// Möbius transform: the coefficient of prod_{i in S} x_i is
+		// sum_{T subset of S} (-1)^{|S| - |T|} f(T)
+		let mut coefficients = mle.evaluations.clone();
+		for bit in 0..n {
+			let step = 1 << bit;
+			for index in 0..coefficients.len() {
+				if index & step != 0 {
+					let lower = coefficients[index - step];
+					coefficients[index] -= lower;
+				}
+			}
+		}
+
+		let terms = coefficients
+			.into_iter()
+			.enumerate()
+			.map(|(index, coefficient)| {
+				let exponents = (0..n).map(|i| (index >> (n - 1 - i)) & 1).collect();
+				SparseMonomial { coefficient, exponents }
+			})
+			.collect();
+		Self::from_terms(n, terms)
+	}
+
+	/// The product of the MLEs of `poly`.
+	pub fn from_composed(poly: &ComposedMultiLinearPolynomial<F>) -> Self {
+		let mut mles = poly.multilineal_polynomial.iter();
+		let first = match mles.next() {
+			Some(mle) => Self::from_mle(mle),
+			None => return Self::zero(0),
+		};
+		mles.fold(first, |product, mle| product * Self::from_mle(mle))
+	}
+
+	/// Fixes `variable` to `value`, leaving a polynomial in one variable fewer.
+	pub fn partial_evaluation(&self, value: F, variable: usize) -> Self {
+		assert!(variable < self.number_of_variables, "Variable index out of range");
+
+		let terms = self
+			.terms
+			.iter()
+			.map(|term| {
+				let mut exponents = term.exponents.clone();
+				let exponent = exponents.remove(variable);
+				SparseMonomial {
+					coefficient: term.coefficient * value.pow([exponent as u64]),
+					exponents,
+				}
+			})
+			.collect();
+		Self::from_terms(self.number_of_variables - 1, terms)
+	}
+
+	pub fn evaluation(&self, point: &[F]) -> F {
+		assert_eq!(
+			point.len(),
+			self.number_of_variables,
+			"Number of evaluation points must match the number of variables"
+		);
+
+		self.terms
+			.iter()
+			.map(|term| {
+				term.exponents
+					.iter()
+					.zip(point.iter())
+					.fold(term.coefficient, |acc, (exponent, x)| acc * x.pow([*exponent as u64]))
+			})
+			.sum()
+	}
+
+	/// The degree in `variable`: its largest exponent over all terms.
+	pub fn degree(&self, variable: usize) -> usize {
+		self.terms.iter().map(|term| term.exponents[variable]).max().unwrap_or(0)
+	}
+
+	/// The total degree: the largest sum of exponents of any term.
+	pub fn total_degree(&self) -> usize {
+		self.terms.iter().map(|term| term.exponents.iter().sum()).max().unwrap_or(0)
+	}
+
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		bytes.extend_from_slice(&(self.number_of_variables as u64).to_le_bytes());
+		for term in self.terms.iter() {
+			bytes.extend_from_slice(&term.coefficient.into_bigint().to_bytes_be());
+			for exponent in term.exponents.iter() {
+				bytes.extend_from_slice(&(*exponent as u64).to_le_bytes());
+			}
+		}
+		bytes
+	}
+}
+
+impl<F: PrimeField> MultivariatePolynomial<F> for SparseMultivariatePolynomial<F> {
+	fn number_of_variables(&self) -> usize {
+		self.number_of_variables
+	}
+
+	fn degree(&self, variable: usize) -> usize {
+		SparseMultivariatePolynomial::degree(self, variable)
+	}
+
+	fn evaluate(&self, point: &[F]) -> F {
+		self.evaluation(point)
+	}
+}
+
+impl<F: PrimeField> Add for SparseMultivariatePolynomial<F> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self {
+		assert_eq!(
+			self.number_of_variables, rhs.number_of_variables,
+			"Polynomials must have the same number of variables"
+		);
+		let mut terms = self.terms;
+		terms.extend(rhs.terms);
+		Self::from_terms(self.number_of_variables, terms)
+	}
+}
+
+impl<F: PrimeField> Mul for SparseMultivariatePolynomial<F> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self {
+		assert_eq!(
+			self.number_of_variables, rhs.number_of_variables,
+			"Polynomials must have the same number of variables"
+		);
+		let mut terms = Vec::with_capacity(self.terms.len() * rhs.terms.len());
+		for lhs_term in self.terms.iter() {
+			for rhs_term in rhs.terms.iter() {
+				terms.push(SparseMonomial {
+					coefficient: lhs_term.coefficient * rhs_term.coefficient,
+					exponents: lhs_term
+						.exponents
+						.iter()
+						.zip(rhs_term.exponents.iter())
+						.map(|(a, b)| a + b)
+						.collect(),
+				});
+			}
+		}
+		Self::from_terms(self.number_of_variables, terms)
+	}
+}
+
+impl<F: PrimeField> Mul<F> for SparseMultivariatePolynomial<F> {
+	type Output = Self;
+
+	fn mul(self, rhs: F) -> Self {
+		let terms = self
+			.terms
+			.into_iter()
+			.map(|term| SparseMonomial { coefficient: term.coefficient * rhs, ..term })
+			.collect();
+		Self::from_terms(self.number_of_variables, terms)
+	}
+}
+
+impl<F: PrimeField> Display for SparseMultivariatePolynomial<F> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		if self.terms.is_empty() {
+			return write!(f, "0");
+		}
+		for (i, term) in self.terms.iter().enumerate() {
+			if i > 0 {
+				write!(f, " + ")?;
+			}
+			write!(f, "{}", term.coefficient)?;
+			for (variable, exponent) in term.exponents.iter().enumerate() {
+				match exponent {
+					0 => {},
+					1 => write!(f, "x{}", variable)?,
+					_ => write!(f, "x{}^{}", variable, exponent)?,
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::composed::interface::ComposedMultilinearInterface;
+	use crate::multilinear::interface::MultiLinearPolynomialEvaluationFormTrait;
+	use ark_ff::MontConfig;
+	use ark_ff::{Fp64, MontBackend};
+
+	#[derive(MontConfig)]
+	#[modulus = "17"]
+	#[generator = "3"]
+	struct FqConfig;
+	type Fq = Fp64<MontBackend<FqConfig, 1>>;
+
+	// g(x1, x2, x3) = 2 x1^3 + x1 x3 + x2 x3
+	fn textbook_poly() -> SparseMultivariatePolynomial<Fq> {
+		SparseMultivariatePolynomial::new(
+			3,
+			vec![
+				(Fq::from(2), vec![3, 0, 0]),
+				(Fq::from(1), vec![1, 0, 1]),
+				(Fq::from(1), vec![0, 1, 1]),
+			],
+		)
+	}
+
+	#[test]
+	fn test_evaluation_and_degrees() {
+		let g = textbook_poly();
+		// 2 * 8 + 2 * 4 + 3 * 4 = 36
+		assert_eq!(g.evaluation(&[Fq::from(2), Fq::from(3), Fq::from(4)]), Fq::from(36));
+		assert_eq!((g.degree(0), g.degree(1), g.degree(2)), (3, 1, 1));
+		assert_eq!(g.max_degree(), 3);
+		assert_eq!(g.total_degree(), 3);
+	}
+
+	#[test]
+	fn test_like_terms_are_merged() {
+		let p = SparseMultivariatePolynomial::new(
+			2,
+			vec![(Fq::from(3), vec![1, 2]), (Fq::from(5), vec![0, 0]), (Fq::from(14), vec![1, 2])],
+		);
+		assert_eq!(p, SparseMultivariatePolynomial::constant(2, Fq::from(5)));
+		assert!((p.clone() * Fq::from(0)).is_zero());
+	}
+
+	#[test]
+	fn test_add_and_mul() {
+		// (x0 + x1) * (x0 - x1) = x0^2 - x1^2
+		let sum = SparseMultivariatePolynomial::new(
+			2,
+			vec![(Fq::from(1), vec![1, 0]), (Fq::from(1), vec![0, 1])],
+		);
+		let difference = SparseMultivariatePolynomial::new(
+			2,
+			vec![(Fq::from(1), vec![1, 0]), (-Fq::from(1), vec![0, 1])],
+		);
+		let expected = SparseMultivariatePolynomial::new(
+			2,
+			vec![(Fq::from(1), vec![2, 0]), (-Fq::from(1), vec![0, 2])],
+		);
+		assert_eq!(sum.clone() * difference.clone(), expected);
+		assert_eq!(
+			sum + difference,
+			SparseMultivariatePolynomial::new(2, vec![(Fq::from(2), vec![1, 0])])
+		);
+	}
+
+	#[test]
+	fn test_partial_evaluation() {
+		// g(x1, 2, x3) = 2 x1^3 + x1 x3 + 2 x3
+		let expected = SparseMultivariatePolynomial::new(
+			2,
+			vec![(Fq::from(2), vec![3, 0]), (Fq::from(1), vec![1, 1]), (Fq::from(2), vec![0, 1])],
+		);
+		assert_eq!(textbook_poly().partial_evaluation(Fq::from(2), 1), expected);
+	}
+
+	#[test]
+	fn test_from_mle_and_composed() {
+		let a = MultiLinearPolynomialEvaluationForm::new(
+			vec![1, 4, 2, 8, 5, 7, 0, 3].into_iter().map(Fq::from).collect(),
+		);
+		let b = MultiLinearPolynomialEvaluationForm::new(
+			vec![3, 3, 6, 1, 2, 9, 4, 4].into_iter().map(Fq::from).collect(),
+		);
+		let point = [Fq::from(5), Fq::from(11), Fq::from(7)];
+
+		let sparse_a = SparseMultivariatePolynomial::from_mle(&a);
+		assert_eq!(sparse_a.evaluation(&point), a.evaluation(&point));
+		assert_eq!(sparse_a.max_degree(), 1);
+
+		let composed = ComposedMultiLinearPolynomial::new(vec![a, b]);
+		let sparse = SparseMultivariatePolynomial::from_composed(&composed);
+		assert_eq!(sparse.evaluation(&point), composed.evaluation(&point));
+		assert_eq!(sparse.max_degree(), 2);
+	}
+}
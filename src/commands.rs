@@ -0,0 +1,270 @@
+use crate::proof_file::{ProofFile, Protocol};
+use ark_ff::PrimeField;
+use gkr::{datastructure::GKRProof, format::CircuitFile, protocol::GKRProtocol};
+use polynomial::multilinear::{
+	evaluation_form::MultiLinearPolynomialEvaluationForm,
+	interface::MultiLinearPolynomialEvaluationFormTrait,
+};
+use serde_json::{json, Value};
+use std::{fs, path::Path};
+use sum_check::{
+	interface::{SumCheckInterface, SumCheckProof},
+	serialization::{ProofEncoding, DEFAULT_MAX_PROOF_SIZE},
+	sumcheck::SumCheck,
+};
+
+/// Outcome of a verify command, a rejected proof is not an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+	Accepted,
+	Rejected(String),
+}
+
+pub fn read_file(path: &Path) -> Result<Vec<u8>, String> {
+	fs::read(path).map_err(|error| format!("{}: {error}", path.display()))
+}
+
+pub fn write_file(path: &Path, bytes: &[u8]) -> Result<(), String> {
+	fs::write(path, bytes).map_err(|error| format!("{}: {error}", path.display()))
+}
+
+/// A circuit in the JSON format if the file name ends in `.json`, otherwise in the text format.
+pub fn load_circuit(path: &Path) -> Result<CircuitFile, String> {
+	let text = String::from_utf8(read_file(path)?)
+		.map_err(|_| format!("{}: not UTF-8", path.display()))?;
+	let circuit = match path.extension().and_then(|extension| extension.to_str()) {
+		Some("json") => CircuitFile::from_json(&text),
+		_ => CircuitFile::from_text(&text),
+	};
+	circuit.map_err(|error| format!("{}: {error}", path.display()))
+}
+
+/// Field elements are decimal strings or JSON integers, either may be negative.
+fn field_from_json<F: PrimeField>(value: &Value) -> Result<F, String> {
+	let text = match value {
+		Value::String(text) => text.clone(),
+		Value::Number(number) if number.is_i64() || number.is_u64() => number.to_string(),
+		_ => return Err(format!("expected a field element, found {value}")),
+	};
+	let (negative, digits) = match text.strip_prefix('-') {
+		Some(digits) => (true, digits),
+		None => (false, text.as_str()),
+	};
+	if digits.is_empty() || !digits.bytes().all(|digit| digit.is_ascii_digit()) {
+		return Err(format!("invalid field element {text:?}"));
+	}
+	let element = F::from_str(digits).map_err(|_| format!("invalid field element {text:?}"))?;
+	Ok(if negative { -element } else { element })
+}
+
+fn field_to_json<F: PrimeField>(element: &F) -> Value {
+	Value::String(element.into_bigint().to_string())
+}
+
+fn load_json(path: &Path) -> Result<Value, String> {
+	serde_json::from_slice(&read_file(path)?)
+		.map_err(|error| format!("{}: {error}", path.display()))
+}
+
+fn field_elements_from_json<F: PrimeField>(value: &Value, path: &Path) -> Result<Vec<F>, String> {
+	value
+		.as_array()
+		.ok_or_else(|| format!("{}: expected an array of field elements", path.display()))?
+		.iter()
+		.map(field_from_json)
+		.collect::<Result<_, _>>()
+		.map_err(|error| format!("{}: {error}", path.display()))
+}
+
+/// A JSON array of field elements.
+pub fn load_field_elements<F: PrimeField>(path: &Path) -> Result<Vec<F>, String> {
+	field_elements_from_json(&load_json(path)?, path)
+}
+
+/// Claimed circuit outputs, a JSON array of field elements or the `{"outputs": [...]}` object
+/// printed by `gkr prove`.
+pub fn load_outputs<F: PrimeField>(path: &Path) -> Result<Vec<F>, String> {
+	let value = load_json(path)?;
+	match value.get("outputs") {
+		Some(outputs) => field_elements_from_json(outputs, path),
+		None => field_elements_from_json(&value, path),
+	}
+}
+
+fn load_mle<F: PrimeField>(path: &Path) -> Result<MultiLinearPolynomialEvaluationForm<F>, String> {
+	let evaluations = load_field_elements::<F>(path)?;
+	if evaluations.len() < 2 || !evaluations.len().is_power_of_two() {
+		return Err(format!(
+			"{}: an MLE needs a power of two number of evaluations, at least two",
+			path.display()
+		));
+	}
+	Ok(MultiLinearPolynomialEvaluationForm::new(evaluations))
+}
+
+fn check_input_length<F>(circuit: &CircuitFile, input: &[F]) -> Result<(), String> {
+	if input.len() != circuit.number_of_inputs {
+		return Err(format!(
+			"circuit takes {} inputs, the input file has {}",
+			circuit.number_of_inputs,
+			input.len()
+		));
+	}
+	Ok(())
+}
+
+/// Proves the circuit on the input, returning the encoded proof and the circuit outputs as JSON.
+pub fn gkr_prove<F: PrimeField>(
+	circuit: &CircuitFile,
+	input: &[F],
+) -> Result<(Vec<u8>, Value), String> {
+	check_input_length(circuit, input)?;
+	let (outputs, proof) = GKRProtocol::prove_with_outputs(&circuit.circuit, input);
+	let outputs: Vec<Value> = outputs.iter().map(field_to_json).collect();
+	Ok((proof.encode()?, json!({ "outputs": outputs })))
+}
+
+pub fn gkr_verify<F: PrimeField>(
+	circuit: &CircuitFile,
+	input: &[F],
+	outputs: &[F],
+	proof: &[u8],
+) -> Result<Verdict, String> {
+	check_input_length(circuit, input)?;
+	let width = circuit.circuit.layer_width(0);
+	if outputs.len() != width {
+		return Err(format!("circuit has {width} outputs, the outputs file has {}", outputs.len()));
+	}
+	let proof = GKRProof::<F>::decode(proof, DEFAULT_MAX_PROOF_SIZE)?;
+	Ok(match GKRProtocol::verify(&circuit.circuit, input, outputs, &proof) {
+		true => Verdict::Accepted,
+		false => Verdict::Rejected("GKR verifier rejected the proof".to_string()),
+	})
+}
+
+/// Proves the sum of the MLE over the hypercube, returning the encoded proof and the sum as JSON.
+pub fn sumcheck_prove<F: PrimeField>(mle_path: &Path) -> Result<(Vec<u8>, Value), String> {
+	let mut sumcheck = SumCheck::new(load_mle::<F>(mle_path)?);
+	sumcheck.calculate_sum();
+	let proof = sumcheck.sum_check_proof();
+	Ok((proof.encode()?, json!({ "sum": field_to_json(&proof.sum) })))
+}
+
+pub fn sumcheck_verify<F: PrimeField>(mle_path: &Path, proof: &[u8]) -> Result<Verdict, String> {
+	let poly = load_mle::<F>(mle_path)?;
+	let proof = SumCheckProof::<F>::decode(proof, DEFAULT_MAX_PROOF_SIZE)?;
+	Ok(match SumCheck::new(poly.clone()).verify(&proof, &poly) {
+		Ok(()) => Verdict::Accepted,
+		Err(error) => Verdict::Rejected(error.to_string()),
+	})
+}
+
+/// A JSON summary of a proof file.
+pub fn inspect<F: PrimeField>(file: &ProofFile) -> Result<Value, String> {
+	let mut summary = json!({
+		"protocol": file.protocol.name(),
+		"field": file.field.name(),
+		"proof_bytes": file.proof.len(),
+	});
+	match file.protocol {
+		Protocol::Gkr => {
+			let proof = GKRProof::<F>::decode(&file.proof, DEFAULT_MAX_PROOF_SIZE)?;
+			summary["layers"] = json!(proof.sumcheck_proofs.len());
+			summary["rounds_per_layer"] = proof
+				.sumcheck_proofs
+				.iter()
+				.map(|sumcheck_proof| sumcheck_proof.round_polys.len())
+				.collect();
+		},
+		Protocol::Sumcheck => {
+			let proof = SumCheckProof::<F>::decode(&file.proof, DEFAULT_MAX_PROOF_SIZE)?;
+			summary["rounds"] = json!(proof.round_poly.len());
+			summary["sum"] = field_to_json(&proof.sum);
+		},
+	}
+	Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::fields::{FieldPreset, Goldilocks};
+
+	fn temp_file(name: &str, contents: &str) -> std::path::PathBuf {
+		let path = std::env::temp_dir().join(format!("zk-cli-{}-{name}", std::process::id()));
+		fs::write(&path, contents).unwrap();
+		path
+	}
+
+	#[test]
+	fn test_field_elements_from_json() {
+		let path = temp_file("elements.json", r#"[1, "2", -1, "-3", "18446744069414584320"]"#);
+		let elements = load_field_elements::<Goldilocks>(&path).unwrap();
+		assert_eq!(
+			elements,
+			vec![
+				Goldilocks::from(1),
+				Goldilocks::from(2),
+				-Goldilocks::from(1),
+				-Goldilocks::from(3),
+				-Goldilocks::from(1)
+			]
+		);
+
+		for bad in [r#"[1.5]"#, r#"["0x10"]"#, r#"{"a": 1}"#, r#"["-"]"#] {
+			fs::write(&path, bad).unwrap();
+			assert!(load_field_elements::<Goldilocks>(&path).is_err(), "{bad}");
+		}
+		fs::remove_file(path).unwrap();
+	}
+
+	#[test]
+	fn test_gkr_prove_and_verify() {
+		let circuit = temp_file(
+			"circuit.txt",
+			"gkr 1\ninputs 3\nlayer mul 0 1\nlayer add 0 1; mul_const(-2) 2\n",
+		);
+		let circuit = load_circuit(&circuit).unwrap();
+		let input: Vec<Goldilocks> = [2, 3, 4].map(Goldilocks::from).to_vec();
+
+		let (proof, outputs) = gkr_prove(&circuit, &input).unwrap();
+		// (2 + 3) * (-8)
+		assert_eq!(outputs, json!({ "outputs": ["18446744069414584281"] }));
+		let outputs_path = temp_file("outputs.json", &outputs.to_string());
+		let claimed = load_outputs::<Goldilocks>(&outputs_path).unwrap();
+		assert_eq!(claimed, vec![-Goldilocks::from(40)]);
+		assert_eq!(gkr_verify(&circuit, &input, &claimed, &proof), Ok(Verdict::Accepted));
+
+		let other: Vec<Goldilocks> = [2, 3, 5].map(Goldilocks::from).to_vec();
+		let rejected = gkr_verify(&circuit, &other, &claimed, &proof);
+		assert!(matches!(rejected, Ok(Verdict::Rejected(_))));
+		let rejected = gkr_verify(&circuit, &input, &[Goldilocks::from(40)], &proof);
+		assert!(matches!(rejected, Ok(Verdict::Rejected(_))));
+		assert!(gkr_verify(&circuit, &input[..2], &claimed, &proof).is_err());
+		assert!(gkr_verify(&circuit, &input, &[], &proof).is_err());
+
+		fs::write(&outputs_path, "[-40]").unwrap();
+		assert_eq!(load_outputs::<Goldilocks>(&outputs_path).unwrap(), claimed);
+		fs::remove_file(outputs_path).unwrap();
+
+		let file = ProofFile { protocol: Protocol::Gkr, field: FieldPreset::Goldilocks, proof };
+		let summary = inspect::<Goldilocks>(&file).unwrap();
+		assert_eq!(summary["layers"], json!(2));
+		assert_eq!(summary["rounds_per_layer"], json!([2, 4]));
+	}
+
+	#[test]
+	fn test_sumcheck_prove_and_verify() {
+		let mle = temp_file("mle.json", r#"[0, 0, 0, 2, 0, 10, 0, 17]"#);
+		let (proof, sum) = sumcheck_prove::<Goldilocks>(&mle).unwrap();
+		assert_eq!(sum, json!({ "sum": "29" }));
+		assert_eq!(sumcheck_verify::<Goldilocks>(&mle, &proof), Ok(Verdict::Accepted));
+
+		fs::write(&mle, r#"[0, 0, 0, 2, 0, 10, 0, 18]"#).unwrap();
+		assert!(matches!(sumcheck_verify::<Goldilocks>(&mle, &proof), Ok(Verdict::Rejected(_))));
+
+		fs::write(&mle, r#"[1, 2, 3]"#).unwrap();
+		assert!(sumcheck_prove::<Goldilocks>(&mle).is_err());
+		fs::remove_file(mle).unwrap();
+	}
+}
@@ -0,0 +1,69 @@
+use ark_ff::{Fp256, Fp64, MontBackend, MontConfig};
+use clap::ValueEnum;
+
+#[derive(MontConfig)]
+#[modulus = "21888242871839275222246405745257275088548364400416459932520367421832929095617"]
+#[generator = "5"]
+pub struct Bn254Config;
+/// Scalar field of BN254.
+pub type Bn254 = Fp256<MontBackend<Bn254Config, 4>>;
+
+#[derive(MontConfig)]
+#[modulus = "52435875175126190479447740508185965837690552500527637822603658699938581184513"]
+#[generator = "7"]
+pub struct Bls12_381Config;
+/// Scalar field of BLS12-381.
+pub type Bls12_381 = Fp256<MontBackend<Bls12_381Config, 4>>;
+
+#[derive(MontConfig)]
+#[modulus = "18446744069414584321"]
+#[generator = "7"]
+pub struct GoldilocksConfig;
+/// `2^64 - 2^32 + 1`.
+pub type Goldilocks = Fp64<MontBackend<GoldilocksConfig, 1>>;
+
+/// The fields the CLI can work over. The discriminant identifies the field in proof files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FieldPreset {
+	Bn254 = 1,
+	#[value(name = "bls12-381")]
+	Bls12_381 = 2,
+	Goldilocks = 3,
+}
+
+impl FieldPreset {
+	pub fn name(&self) -> &'static str {
+		match self {
+			FieldPreset::Bn254 => "bn254",
+			FieldPreset::Bls12_381 => "bls12-381",
+			FieldPreset::Goldilocks => "goldilocks",
+		}
+	}
+
+	pub fn from_id(id: u8) -> Option<Self> {
+		[FieldPreset::Bn254, FieldPreset::Bls12_381, FieldPreset::Goldilocks]
+			.into_iter()
+			.find(|preset| *preset as u8 == id)
+	}
+}
+
+/// Runs `$body` with `$field` bound to the field type of `$preset`.
+macro_rules! with_field {
+	($preset:expr, $field:ident => $body:expr) => {
+		match $preset {
+			$crate::fields::FieldPreset::Bn254 => {
+				type $field = $crate::fields::Bn254;
+				$body
+			},
+			$crate::fields::FieldPreset::Bls12_381 => {
+				type $field = $crate::fields::Bls12_381;
+				$body
+			},
+			$crate::fields::FieldPreset::Goldilocks => {
+				type $field = $crate::fields::Goldilocks;
+				$body
+			},
+		}
+	};
+}
+pub(crate) use with_field;
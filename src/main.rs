@@ -1,3 +1,176 @@
-fn main(){
-    println!("zk :)");
-}
\ No newline at end of file
+//! `zk`, a command line front end for the GKR and sum-check provers.
+//!
+//! ```text
+//! zk gkr prove --circuit c.json --input in.json --out proof.bin [--field bn254]
+//! zk gkr verify --circuit c.json --input in.json --outputs out.json --proof proof.bin
+//! zk sumcheck prove --mle mle.json --out proof.bin [--field goldilocks]
+//! zk sumcheck verify --mle mle.json --proof proof.bin
+//! zk inspect proof.bin
+//! ```
+//!
+//! Circuits are read in the JSON format when the file ends in `.json` and in the text format
+//! otherwise, see `gkr::format`. Inputs and MLEs are JSON arrays of field elements, written as
+//! integers or decimal strings. The claimed outputs may also be given as the JSON `gkr prove`
+//! prints. Proof files record their field, so verifying and inspecting need no `--field`.
+//!
+//! Results are printed as JSON on stdout. The exit code is 0 on success, 1 when a proof is
+//! rejected and 2 on any other error.
+
+mod commands;
+mod fields;
+mod proof_file;
+
+use clap::{Parser, Subcommand};
+use commands::Verdict;
+use fields::{with_field, FieldPreset};
+use proof_file::{ProofFile, Protocol};
+use serde_json::json;
+use std::{
+	path::{Path, PathBuf},
+	process::ExitCode,
+};
+
+#[derive(Parser)]
+#[command(name = "zk", about = "Prove and verify GKR and sum-check instances")]
+struct Cli {
+	#[command(subcommand)]
+	command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+	/// GKR proofs for layered arithmetic circuits
+	#[command(subcommand)]
+	Gkr(GkrCommand),
+	/// Sum-check proofs for the hypercube sum of a multilinear polynomial
+	#[command(subcommand)]
+	Sumcheck(SumcheckCommand),
+	/// Print a summary of a proof file
+	Inspect { proof: PathBuf },
+}
+
+#[derive(Subcommand)]
+enum GkrCommand {
+	Prove {
+		#[arg(long)]
+		circuit: PathBuf,
+		#[arg(long)]
+		input: PathBuf,
+		#[arg(long)]
+		out: PathBuf,
+		#[arg(long, value_enum, default_value = "bn254")]
+		field: FieldPreset,
+	},
+	Verify {
+		#[arg(long)]
+		circuit: PathBuf,
+		#[arg(long)]
+		input: PathBuf,
+		/// The claimed values of the output layer
+		#[arg(long)]
+		outputs: PathBuf,
+		#[arg(long)]
+		proof: PathBuf,
+	},
+}
+
+#[derive(Subcommand)]
+enum SumcheckCommand {
+	Prove {
+		/// JSON array with the evaluations of the MLE over the hypercube
+		#[arg(long)]
+		mle: PathBuf,
+		#[arg(long)]
+		out: PathBuf,
+		#[arg(long, value_enum, default_value = "bn254")]
+		field: FieldPreset,
+	},
+	Verify {
+		#[arg(long)]
+		mle: PathBuf,
+		#[arg(long)]
+		proof: PathBuf,
+	},
+}
+
+fn write_proof(
+	path: &Path,
+	protocol: Protocol,
+	field: FieldPreset,
+	proof: Vec<u8>,
+) -> Result<(), String> {
+	commands::write_file(path, &ProofFile { protocol, field, proof }.to_bytes())
+}
+
+fn read_proof(path: &Path) -> Result<ProofFile, String> {
+	ProofFile::from_bytes(&commands::read_file(path)?)
+		.map_err(|error| format!("{}: {error}", path.display()))
+}
+
+fn report(verdict: Verdict) -> ExitCode {
+	match verdict {
+		Verdict::Accepted => {
+			println!("{}", json!({ "verified": true }));
+			ExitCode::SUCCESS
+		},
+		Verdict::Rejected(reason) => {
+			println!("{}", json!({ "verified": false, "reason": reason }));
+			ExitCode::from(1)
+		},
+	}
+}
+
+fn run(command: Command) -> Result<ExitCode, String> {
+	match command {
+		Command::Gkr(GkrCommand::Prove { circuit, input, out, field }) => {
+			let circuit = commands::load_circuit(&circuit)?;
+			let (proof, outputs) = with_field!(field, F => {
+				let input = commands::load_field_elements::<F>(&input)?;
+				commands::gkr_prove::<F>(&circuit, &input)?
+			});
+			write_proof(&out, Protocol::Gkr, field, proof)?;
+			println!("{outputs}");
+			Ok(ExitCode::SUCCESS)
+		},
+		Command::Gkr(GkrCommand::Verify { circuit, input, outputs, proof }) => {
+			let circuit = commands::load_circuit(&circuit)?;
+			let file = read_proof(&proof)?;
+			let proof = file.expect(Protocol::Gkr)?;
+			let verdict = with_field!(file.field, F => {
+				let input = commands::load_field_elements::<F>(&input)?;
+				let outputs = commands::load_outputs::<F>(&outputs)?;
+				commands::gkr_verify::<F>(&circuit, &input, &outputs, proof)?
+			});
+			Ok(report(verdict))
+		},
+		Command::Sumcheck(SumcheckCommand::Prove { mle, out, field }) => {
+			let (proof, sum) = with_field!(field, F => commands::sumcheck_prove::<F>(&mle)?);
+			write_proof(&out, Protocol::Sumcheck, field, proof)?;
+			println!("{sum}");
+			Ok(ExitCode::SUCCESS)
+		},
+		Command::Sumcheck(SumcheckCommand::Verify { mle, proof }) => {
+			let file = read_proof(&proof)?;
+			let proof = file.expect(Protocol::Sumcheck)?;
+			let verdict =
+				with_field!(file.field, F => commands::sumcheck_verify::<F>(&mle, proof)?);
+			Ok(report(verdict))
+		},
+		Command::Inspect { proof } => {
+			let file = read_proof(&proof)?;
+			let summary = with_field!(file.field, F => commands::inspect::<F>(&file)?);
+			println!("{summary}");
+			Ok(ExitCode::SUCCESS)
+		},
+	}
+}
+
+fn main() -> ExitCode {
+	match run(Cli::parse().command) {
+		Ok(code) => code,
+		Err(error) => {
+			eprintln!("error: {error}");
+			ExitCode::from(2)
+		},
+	}
+}
@@ -0,0 +1,98 @@
+//! Proof files written by the CLI.
+//!
+//! | bytes | content                                      |
+//! |-------|----------------------------------------------|
+//! | 4     | magic `ZKPF`                                 |
+//! | 1     | file version, currently `1`                  |
+//! | 1     | protocol, see [`Protocol`]                   |
+//! | 1     | field, see [`FieldPreset`]                   |
+//! |       | the proof in the protocol's binary encoding  |
+
+use crate::fields::FieldPreset;
+
+pub const MAGIC: &[u8; 4] = b"ZKPF";
+pub const PROOF_FILE_VERSION: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+	Gkr = 1,
+	Sumcheck = 2,
+}
+
+impl Protocol {
+	pub fn name(&self) -> &'static str {
+		match self {
+			Protocol::Gkr => "gkr",
+			Protocol::Sumcheck => "sumcheck",
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofFile {
+	pub protocol: Protocol,
+	pub field: FieldPreset,
+	pub proof: Vec<u8>,
+}
+
+impl ProofFile {
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut bytes = MAGIC.to_vec();
+		bytes.extend_from_slice(&[PROOF_FILE_VERSION, self.protocol as u8, self.field as u8]);
+		bytes.extend_from_slice(&self.proof);
+		bytes
+	}
+
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+		let Some((header, proof)) = bytes.split_at_checked(7) else {
+			return Err("Not a proof file");
+		};
+		if &header[..4] != MAGIC {
+			return Err("Not a proof file");
+		}
+		if header[4] != PROOF_FILE_VERSION {
+			return Err("Unsupported proof file version");
+		}
+		let protocol = match header[5] {
+			1 => Protocol::Gkr,
+			2 => Protocol::Sumcheck,
+			_ => return Err("Unknown protocol"),
+		};
+		let field = FieldPreset::from_id(header[6]).ok_or("Unknown field")?;
+		Ok(Self { protocol, field, proof: proof.to_vec() })
+	}
+
+	/// The proof if the file holds one for `protocol`.
+	pub fn expect(&self, protocol: Protocol) -> Result<&[u8], String> {
+		if self.protocol != protocol {
+			return Err(format!(
+				"expected a {} proof, found a {} proof",
+				protocol.name(),
+				self.protocol.name()
+			));
+		}
+		Ok(&self.proof)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_proof_file_round_trip() {
+		let file = ProofFile {
+			protocol: Protocol::Sumcheck,
+			field: FieldPreset::Goldilocks,
+			proof: vec![2, 1, 0],
+		};
+		let bytes = file.to_bytes();
+		assert_eq!(&bytes[..7], b"ZKPF\x01\x02\x03");
+		assert_eq!(ProofFile::from_bytes(&bytes), Ok(file.clone()));
+
+		assert!(file.expect(Protocol::Gkr).is_err());
+		assert_eq!(ProofFile::from_bytes(b"ZKPF\x01\x02"), Err("Not a proof file"));
+		assert_eq!(ProofFile::from_bytes(b"ZKPF\x01\x02\x09"), Err("Unknown field"));
+		assert_eq!(ProofFile::from_bytes(b"PK\x03\x04\x01\x02\x03"), Err("Not a proof file"));
+	}
+}
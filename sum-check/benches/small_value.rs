@@ -0,0 +1,66 @@
+use ark_ff::{Fp256, MontBackend, MontConfig};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use polynomial::composed::interface::ComposedMultilinearInterface;
+use polynomial::composed::multilinear::ComposedMultiLinearPolynomial;
+use polynomial::multilinear::evaluation_form::MultiLinearPolynomialEvaluationForm;
+use polynomial::multilinear::interface::MultiLinearPolynomialEvaluationFormTrait;
+use sum_check::interactive::SumcheckProverState;
+
+// BN254 scalar field
+#[derive(MontConfig)]
+#[modulus = "21888242871839275222246405745257275088548364400416459932520367421832929095617"]
+#[generator = "5"]
+struct FrConfig;
+type Fr = Fp256<MontBackend<FrConfig, 4>>;
+
+/// A product of `degree` tables in `number_of_variables` variables with entries below `bound`.
+fn small_poly(
+	number_of_variables: usize,
+	degree: usize,
+	bound: u64,
+) -> ComposedMultiLinearPolynomial<Fr> {
+	let mles = (0..degree as u64)
+		.map(|j| {
+			let evaluations = (0..1u64 << number_of_variables)
+				.map(|i| Fr::from((i.wrapping_mul(2654435761).wrapping_add(j * 97) >> 7) % bound))
+				.collect();
+			MultiLinearPolynomialEvaluationForm::new(evaluations)
+		})
+		.collect();
+	ComposedMultiLinearPolynomial::new(mles)
+}
+
+fn run(mut prover: SumcheckProverState<Fr>) {
+	let mut challenge = None;
+	while !prover.is_finished() {
+		let message = prover.next_round(challenge);
+		challenge = Some(message.evaluations[0] + Fr::from(7u64));
+	}
+}
+
+fn bench_first_round(c: &mut Criterion) {
+	let mut group = c.benchmark_group("sumcheck_small_value");
+	group.sample_size(10);
+
+	for (name, bound) in [("bits", 2), ("u16", 1 << 16)] {
+		for degree in [2, 3] {
+			let poly = vec![small_poly(16, degree, bound)];
+			let id = format!("{name}/degree_{degree}");
+
+			group.bench_with_input(BenchmarkId::new("field", &id), &poly, |b, poly| {
+				b.iter(|| run(SumcheckProverState::new(black_box(poly.clone()))))
+			});
+			group.bench_with_input(BenchmarkId::new("small_value", &id), &poly, |b, poly| {
+				b.iter(|| {
+					run(SumcheckProverState::new(black_box(poly.clone()))
+						.with_small_value_first_round())
+				})
+			});
+		}
+	}
+
+	group.finish();
+}
+
+criterion_group!(benches, bench_first_round);
+criterion_main!(benches);
@@ -1,12 +1,13 @@
 // use crate::interface::ComposedSumCheckProof;
-use crate::util::transform_round_poly_to_uni_poly;
-use ark_ff::{BigInteger, PrimeField};
+use crate::error::SumcheckError;
+use crate::oracle::EvaluationOracle;
+use crate::statement::{SumcheckStatement, COMPOSED_SUMCHECK_PROTOCOL};
+use crate::interactive::{
+    fiat_shamir_challenge, RoundMessage, SumcheckProverState, SumcheckVerifierState,
+};
+use ark_ff::PrimeField;
 use polynomial::composed::interface::ComposedMultilinearInterface;
 use polynomial::composed::multilinear::ComposedMultiLinearPolynomial;
-use polynomial::interface::UnivariatePolynomialTrait;
-use polynomial::multilinear::evaluation_form::MultiLinearPolynomialEvaluationForm;
-use polynomial::multilinear::interface::MultiLinearPolynomialEvaluationFormTrait;
-use polynomial::univariate_polynomial::univariate::UnivariatePolynomial;
 use transcript::transcription::Transcript;
 
 use crate::interface::ComposedSumCheckInterface;
@@ -16,9 +17,9 @@ pub struct ComposedSumcheck<F: PrimeField> {
     pub sum: F,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ComposedSumcheckProof<F: PrimeField> {
-    polynomial: ComposedMultiLinearPolynomial<F>,
-    round_polys: Vec<Vec<F>>,
+    pub round_polys: Vec<Vec<F>>,
 }
 
 impl<F: PrimeField> ComposedSumcheck<F> {
@@ -32,79 +33,101 @@ impl<F: PrimeField> ComposedSumcheck<F> {
         poly.elementwise_product().iter().sum()
     }
 	pub fn prove(&self) -> (ComposedSumcheckProof<F>, Vec<F>) {
-        let mut transcript = Transcript::new();
+        self.prove_with_transcript(&mut Transcript::new())
+    }
 
-        let mut current_poly: ComposedMultiLinearPolynomial<F> = self.polynomial.clone();
-        let mut round_polys: Vec<Vec<F>> = vec![];
-        let mut challenges: Vec<F> = vec![];
+    /// Like [`ComposedSumcheck::prove`], continuing a transcript owned by an outer protocol. The
+    /// statement carries the bytes of the polynomial as its digest.
+    pub fn prove_with_transcript(
+        &self,
+        transcript: &mut Transcript,
+    ) -> (ComposedSumcheckProof<F>, Vec<F>) {
+        let sum = Self::calculate_sum(&self.polynomial);
+        let statement =
+            SumcheckStatement::for_oracle(COMPOSED_SUMCHECK_PROTOCOL, &self.polynomial, sum);
+        self.prove_statement(&statement, transcript)
+            .expect("statement is built from the polynomial")
+    }
 
-        for _ in 0..self.polynomial.number_of_variables() {
-            let mut round_poly: Vec<F> = vec![];
-            for i in 0..=current_poly.max_degree() {
-                let round: F = current_poly
-                    .partial_evaluation(F::from(i as u32), 0)
-                   .elementwise_product()
-                    .iter()
-                    .sum::<F>();
+    /// Absorbs `statement`, e.g. one binding a commitment to the polynomial, and proves it.
+    pub fn prove_statement(
+        &self,
+        statement: &SumcheckStatement<F>,
+        transcript: &mut Transcript,
+    ) -> Result<(ComposedSumcheckProof<F>, Vec<F>), &'static str> {
+        let number_of_variables = ComposedMultilinearInterface::number_of_variables(&self.polynomial);
+        if number_of_variables != statement.number_of_variables {
+            return Err("Statement has a different number of variables than the polynomial");
+        }
+        if self.polynomial.max_degree() > statement.degree {
+            return Err("Polynomial degree exceeds the statement's degree bound");
+        }
 
-                round_poly.push(round);
-            }
+        statement.absorb(transcript);
 
-            transcript.append(&vec_to_bytes(&round_poly));
+        let mut prover =
+            SumcheckProverState::new(vec![self.polynomial.clone()]).with_small_value_first_round();
+        let mut round_polys: Vec<Vec<F>> = vec![];
+        let mut challenges: Vec<F> = vec![];
+
+        while !prover.is_finished() {
+            let message = prover.next_round(challenges.last().copied());
             //get the random r
-            let random_r: F = transcript.transform_challenge_to_field::<F>();
+            let random_r: F = fiat_shamir_challenge(transcript, &message);
             challenges.push(random_r);
-            round_polys.push(round_poly);
-
-            current_poly = current_poly.partial_evaluation(random_r, 0);
+            round_polys.push(message.evaluations);
         }
 
-        (
-            ComposedSumcheckProof {
-                polynomial: self.polynomial.clone(),
-                round_polys,
-            },
-            challenges,
-        )
+        Ok((ComposedSumcheckProof { round_polys }, challenges))
     }
 
-	pub fn verify(&self, proof: &ComposedSumcheckProof<F>, sum: F) -> bool {
-        let mut transcript = Transcript::new();
+    /// Checks `proof` for `sum` against whatever `oracle` stands for, the polynomial itself or a
+    /// commitment to it. The statement takes its digest from `oracle`.
+    pub fn verify(
+        proof: &ComposedSumcheckProof<F>,
+        sum: F,
+        oracle: &dyn EvaluationOracle<F>,
+    ) -> Result<(), SumcheckError<F>> {
+        Self::verify_with_transcript(proof, sum, oracle, &mut Transcript::new())
+    }
 
-        let mut claimed_sum = sum;
-        let mut challenges: Vec<F> = vec![];
+    pub fn verify_with_transcript(
+        proof: &ComposedSumcheckProof<F>,
+        sum: F,
+        oracle: &dyn EvaluationOracle<F>,
+        transcript: &mut Transcript,
+    ) -> Result<(), SumcheckError<F>> {
+        let statement = SumcheckStatement::for_oracle(COMPOSED_SUMCHECK_PROTOCOL, oracle, sum);
+        statement.absorb(transcript);
 
-        for round_poly in proof.round_polys.iter() {
-            transcript.append(&vec_to_bytes(&round_poly));
-            // genrate the challenge for this round
-            let challenge: F = transcript.transform_challenge_to_field::<F>();
-            challenges.push(challenge);
-
-            let round_polys_uni: Vec<(F, F)> = transform_round_poly_to_uni_poly(&round_poly);
-            let uni_poly: UnivariatePolynomial<F> =
-                UnivariatePolynomial::interpolate(&round_polys_uni);
+        let number_of_variables = oracle.number_of_variables();
+        if proof.round_polys.len() != number_of_variables {
+            return Err(SumcheckError::WrongRoundCount {
+                expected: number_of_variables,
+                got: proof.round_polys.len(),
+            });
+        }
 
-            let eval_p0_p1 = uni_poly.evaluate(F::zero()) + uni_poly.evaluate(F::one());
-            if claimed_sum != eval_p0_p1 {
-                return false;
-            }
+        let mut verifier = SumcheckVerifierState::new(
+            number_of_variables,
+            sum,
+            statement.degree_bounds(),
+            transcript,
+        );
+        for round_poly in proof.round_polys.iter() {
+            verifier.receive(&RoundMessage::new(round_poly.clone()))?;
+        }
 
-            // update the sum
-            claimed_sum = uni_poly.evaluate(challenge);
+        let sub_claim = verifier.finalize()?;
+        if oracle.evaluate(&sub_claim.challenges)? != sub_claim.sum {
+            return Err(SumcheckError::FinalEvaluationMismatch);
         }
 
-        proof.polynomial.evaluation(challenges.as_slice()) == claimed_sum
+        Ok(())
     }
 }
 
 
-fn vec_to_bytes<F: PrimeField>(poly: &Vec<F>) -> Vec<u8> {
-	let mut bytes = Vec::new();
-	for p in poly {
-		bytes.extend_from_slice(&p.into_bigint().to_bytes_be());
-	}
-	bytes
-}
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,6 +135,7 @@ mod tests {
     use ark_ff::{Fp64, MontBackend};
     use polynomial::interface::MLETrait;
 	use polynomial::multilinear::evaluation_form::MultiLinearPolynomialEvaluationForm;
+    use polynomial::multilinear::interface::MultiLinearPolynomialEvaluationFormTrait;
 
     #[derive(MontConfig)]
     #[modulus = "17"]
@@ -171,9 +195,9 @@ mod tests {
         let composedmle = ComposedMultiLinearPolynomial::new(vec![mle1, mle2]);
         let sumcheck = ComposedSumcheck::new(composedmle);
         let (proof, _challenges) = &sumcheck.prove();
-        let sum = ComposedSumcheck::calculate_sum(&proof.polynomial);
-        let verifer: bool = sumcheck.verify(proof, sum);
-        assert_eq!(verifer, true);
+        let sum = ComposedSumcheck::calculate_sum(&sumcheck.polynomial);
+        let verifer = ComposedSumcheck::verify(proof, sum, &sumcheck.polynomial);
+        assert_eq!(verifer, Ok(()));
     }
 
     #[test]
@@ -191,9 +215,9 @@ mod tests {
         let composedmle = ComposedMultiLinearPolynomial::new(vec![mle]);
         let sumcheck = ComposedSumcheck::new(composedmle);
         let (proof, _challenges) = &sumcheck.prove();
-        let sum = ComposedSumcheck::calculate_sum(&proof.polynomial);
-        let verifer: bool = sumcheck.verify(&proof, sum);
-        assert_eq!(verifer, true);
+        let sum = ComposedSumcheck::calculate_sum(&sumcheck.polynomial);
+        let verifer = ComposedSumcheck::verify(&proof, sum, &sumcheck.polynomial);
+        assert_eq!(verifer, Ok(()));
     }
 
     #[test]
@@ -219,10 +243,10 @@ mod tests {
         let composedmle = ComposedMultiLinearPolynomial::new(vec![mle]);
         let sumcheck = ComposedSumcheck::new(composedmle);
         let proof = sumcheck.prove();
-        let sum = ComposedSumcheck::calculate_sum(&proof.0.polynomial);
-        let verifer = sumcheck.verify(&proof.0, sum);
+        let sum = ComposedSumcheck::calculate_sum(&sumcheck.polynomial);
+        let verifer = ComposedSumcheck::verify(&proof.0, sum, &sumcheck.polynomial);
 
-        assert_eq!(verifer, true);
+        assert_eq!(verifer, Ok(()));
     }
 
     #[test]
@@ -248,9 +272,24 @@ mod tests {
         let composedmle = ComposedMultiLinearPolynomial::new(vec![mle]);
         let sumcheck = ComposedSumcheck::new(composedmle);
         let proof = sumcheck.prove();
-        let sum = ComposedSumcheck::calculate_sum(&proof.0.polynomial);
-        let verifer = sumcheck.verify(&proof.0, sum);
+        let sum = ComposedSumcheck::calculate_sum(&sumcheck.polynomial);
+        let verifer = ComposedSumcheck::verify(&proof.0, sum, &sumcheck.polynomial);
+
+        assert_eq!(verifer, Ok(()));
+    }
+
+    #[test]
+    fn test_sum_check_rejects_oversized_round_polynomial() {
+        let mle1 = MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(3), Fq::from(3), Fq::from(5), Fq::from(5)]);
+        let mle2 = MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(0), Fq::from(0), Fq::from(0), Fq::from(1)]);
+        let sumcheck = ComposedSumcheck::new(ComposedMultiLinearPolynomial::new(vec![mle1, mle2]));
+        let (mut proof, _challenges) = sumcheck.prove();
+        let sum = ComposedSumcheck::calculate_sum(&sumcheck.polynomial);
+        proof.round_polys[1].push(Fq::from(0));
 
-        assert_eq!(verifer, true);
+        assert_eq!(
+            ComposedSumcheck::verify(&proof, sum, &sumcheck.polynomial),
+            Err(SumcheckError::DegreeTooHigh { round: 1 })
+        );
     }
 }
@@ -0,0 +1,293 @@
+use crate::error::SumcheckError;
+use crate::interactive::{
+	fiat_shamir_challenge, RoundMessage, SumcheckProverState, SumcheckVerifierState,
+};
+use crate::multi_composedsumcheck::{ComposedSumcheckProof, SubClaim};
+use crate::oracle::EvaluationOracle;
+use crate::statement::{SumcheckStatement, DOMAIN_SUMCHECK_PROTOCOL};
+use crate::util::vec_to_bytes;
+use ark_ff::PrimeField;
+use polynomial::composed::interface::ComposedMultilinearInterface;
+use polynomial::composed::multilinear::ComposedMultiLinearPolynomial;
+use transcript::transcription::Transcript;
+
+/// The points of the multiplicative subgroup of order `size`, `1, w, w^2, ...`, or `None` if the
+/// field has no such subgroup.
+pub fn multiplicative_subgroup<F: PrimeField>(size: usize) -> Option<Vec<F>> {
+	let generator = F::get_root_of_unity(size as u64)?;
+	Some(
+		std::iter::successors(Some(F::one()), |h| Some(*h * generator))
+			.take(size)
+			.collect(),
+	)
+}
+
+fn check_domain<F: PrimeField>(domain: &[F]) -> Result<(), &'static str> {
+	if domain.is_empty() {
+		return Err("Domain needs at least one point");
+	}
+	for (i, h) in domain.iter().enumerate() {
+		if domain[i + 1..].contains(h) {
+			return Err("Domain points must be distinct");
+		}
+	}
+	Ok(())
+}
+
+/// The domain is part of the statement, absorb it right after the statement header.
+fn absorb_domain<F: PrimeField>(domain: &[F], transcript: &mut Transcript) {
+	transcript.append(&(domain.len() as u64).to_le_bytes());
+	transcript.append(&vec_to_bytes(&domain.to_vec()));
+}
+
+/// Sum-check for `sum_{x in H^n} sum_i prod_j f_ij(x)` over an arbitrary set `H` of field elements,
+/// e.g. `{0, 1, 2}` or a multiplicative subgroup. With `H = {0, 1}` it is the multi-composed
+/// sum-check.
+///
+/// Each round the verifier checks `sum_{h in H} s(h)` against the claim, the rest of the protocol
+/// is unchanged. The prover's work grows with `|H|^n`, so `H` is meant to be small.
+pub struct DomainSumcheckProver {}
+
+impl DomainSumcheckProver {
+	pub fn calculate_poly_sum<F: PrimeField>(
+		poly: &[ComposedMultiLinearPolynomial<F>],
+		domain: &[F],
+	) -> F {
+		let mut points: Vec<Vec<F>> = vec![vec![]];
+		for _ in 0..poly.number_of_variables() {
+			points = points
+				.iter()
+				.flat_map(|point| {
+					domain.iter().map(move |h| {
+						let mut next = point.clone();
+						next.push(*h);
+						next
+					})
+				})
+				.collect();
+		}
+
+		points
+			.iter()
+			.map(|point| poly.iter().map(|p| p.evaluation(point)).sum::<F>())
+			.sum()
+	}
+
+	pub fn prove<F: PrimeField>(
+		poly: &[ComposedMultiLinearPolynomial<F>],
+		domain: &[F],
+		sum: &F,
+	) -> Result<(ComposedSumcheckProof<F>, Vec<F>), &'static str> {
+		DomainSumcheckProver::prove_with_transcript(poly, domain, sum, &mut Transcript::new())
+	}
+
+	/// Like [`DomainSumcheckProver::prove`], continuing a transcript owned by an outer protocol.
+	pub fn prove_with_transcript<F: PrimeField>(
+		poly: &[ComposedMultiLinearPolynomial<F>],
+		domain: &[F],
+		sum: &F,
+		transcript: &mut Transcript,
+	) -> Result<(ComposedSumcheckProof<F>, Vec<F>), &'static str> {
+		let statement = SumcheckStatement::for_oracle(DOMAIN_SUMCHECK_PROTOCOL, poly, *sum);
+		DomainSumcheckProver::prove_internal(poly, domain, &statement, transcript)
+	}
+
+	pub fn prove_internal<F: PrimeField>(
+		poly: &[ComposedMultiLinearPolynomial<F>],
+		domain: &[F],
+		statement: &SumcheckStatement<F>,
+		transcript: &mut Transcript,
+	) -> Result<(ComposedSumcheckProof<F>, Vec<F>), &'static str> {
+		if poly.is_empty() {
+			return Err("Sum-check needs at least one composed polynomial");
+		}
+		if poly.number_of_variables() != statement.number_of_variables {
+			return Err("Statement has a different number of variables than the polynomial");
+		}
+		if poly.degree() > statement.degree {
+			return Err("Polynomial degree exceeds the statement's degree bound");
+		}
+		check_domain(domain)?;
+
+		statement.absorb(transcript);
+		absorb_domain(domain, transcript);
+
+		let mut prover = SumcheckProverState::new(poly.to_vec()).with_domain(domain.to_vec());
+		let mut round_polys = vec![];
+		let mut challenges: Vec<F> = vec![];
+
+		while !prover.is_finished() {
+			let round_poly = prover.next_round(challenges.last().copied()).to_univariate();
+
+			let message = RoundMessage::from_univariate(&round_poly)?;
+			challenges.push(fiat_shamir_challenge(transcript, &message));
+			round_polys.push(round_poly);
+		}
+
+		Ok((ComposedSumcheckProof { round_polys, sum: statement.sum }, challenges))
+	}
+}
+
+pub struct DomainSumcheckVerifier {}
+
+impl DomainSumcheckVerifier {
+	pub fn verify<F: PrimeField>(
+		poly: &[ComposedMultiLinearPolynomial<F>],
+		domain: &[F],
+		proof: &ComposedSumcheckProof<F>,
+	) -> Result<(), SumcheckError<F>> {
+		Self::verify_with_transcript(poly, domain, proof, &mut Transcript::new())
+	}
+
+	pub fn verify_with_transcript<F: PrimeField>(
+		poly: &[ComposedMultiLinearPolynomial<F>],
+		domain: &[F],
+		proof: &ComposedSumcheckProof<F>,
+		transcript: &mut Transcript,
+	) -> Result<(), SumcheckError<F>> {
+		let statement = SumcheckStatement::for_oracle(DOMAIN_SUMCHECK_PROTOCOL, poly, proof.sum);
+		let sub_claim = Self::verify_internal(proof, domain, &statement, transcript)?;
+
+		if poly.evaluate(&sub_claim.challenges)? != sub_claim.sum {
+			return Err(SumcheckError::FinalEvaluationMismatch);
+		}
+
+		Ok(())
+	}
+
+	/// Absorbs `statement` and `domain` and checks the rounds of `proof`, returning the claim left
+	/// for the final evaluation.
+	pub fn verify_internal<F: PrimeField>(
+		proof: &ComposedSumcheckProof<F>,
+		domain: &[F],
+		statement: &SumcheckStatement<F>,
+		transcript: &mut Transcript,
+	) -> Result<SubClaim<F>, SumcheckError<F>> {
+		check_domain(domain).map_err(SumcheckError::MalformedProof)?;
+		if proof.sum != statement.sum {
+			return Err(SumcheckError::ClaimedSumMismatch {
+				expected: statement.sum,
+				got: proof.sum,
+			});
+		}
+		if proof.round_polys.len() != statement.number_of_variables {
+			return Err(SumcheckError::WrongRoundCount {
+				expected: statement.number_of_variables,
+				got: proof.round_polys.len(),
+			});
+		}
+
+		statement.absorb(transcript);
+		absorb_domain(domain, transcript);
+
+		let mut verifier = SumcheckVerifierState::new(
+			statement.number_of_variables,
+			statement.sum,
+			statement.degree_bounds(),
+			&mut *transcript,
+		)
+		.with_domain(domain.to_vec());
+
+		for (round, round_poly) in proof.round_polys.iter().enumerate() {
+			let message =
+				RoundMessage::from_univariate_bounded(round_poly, statement.degree_bound(round))
+					.map_err(|_| SumcheckError::DegreeTooHigh { round })?;
+			verifier.receive(&message)?;
+		}
+
+		verifier.finalize()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::multi_composedsumcheck::{
+		MultiComposedSumcheckProver, MultiComposedSumcheckVerifier,
+	};
+	use ark_ff::MontConfig;
+	use ark_ff::{Fp64, MontBackend};
+	use polynomial::multilinear::evaluation_form::MultiLinearPolynomialEvaluationForm;
+	use polynomial::multilinear::interface::MultiLinearPolynomialEvaluationFormTrait;
+
+	#[derive(MontConfig)]
+	#[modulus = "17"]
+	#[generator = "3"]
+	struct FqConfig;
+	type Fq = Fp64<MontBackend<FqConfig, 1>>;
+
+	fn sample_poly() -> Vec<ComposedMultiLinearPolynomial<Fq>> {
+		let to_mle = |v: Vec<u64>| {
+			MultiLinearPolynomialEvaluationForm::new(v.into_iter().map(Fq::from).collect())
+		};
+		let a = to_mle(vec![1, 4, 2, 8, 5, 7, 0, 3]);
+		let b = to_mle(vec![3, 3, 6, 1, 2, 9, 4, 4]);
+
+		vec![
+			ComposedMultiLinearPolynomial::new(vec![a.clone(), b.clone()]),
+			ComposedMultiLinearPolynomial::new(vec![b]),
+		]
+	}
+
+	#[test]
+	fn test_sum_over_ternary_domain() {
+		let poly = sample_poly();
+		let domain = vec![Fq::from(0), Fq::from(1), Fq::from(2)];
+		let sum = DomainSumcheckProver::calculate_poly_sum(&poly, &domain);
+
+		let (proof, challenges) = DomainSumcheckProver::prove(&poly, &domain, &sum).unwrap();
+		assert_eq!(proof.round_polys.len(), 3);
+		assert_eq!(challenges.len(), 3);
+		assert_eq!(DomainSumcheckVerifier::verify(&poly, &domain, &proof), Ok(()));
+
+		// the same proof does not check against a different domain or a different sum
+		let other_domain = vec![Fq::from(0), Fq::from(1), Fq::from(3)];
+		assert!(DomainSumcheckVerifier::verify(&poly, &other_domain, &proof).is_err());
+
+		let (wrong, _) = DomainSumcheckProver::prove(&poly, &domain, &(sum + Fq::from(1))).unwrap();
+		assert!(matches!(
+			DomainSumcheckVerifier::verify(&poly, &domain, &wrong),
+			Err(SumcheckError::RoundSumMismatch { round: 0, .. })
+		));
+	}
+
+	#[test]
+	fn test_sum_over_multiplicative_subgroup() {
+		let poly = sample_poly();
+		let domain: Vec<Fq> = multiplicative_subgroup(4).unwrap();
+		assert_eq!(domain, vec![Fq::from(1), Fq::from(13), Fq::from(16), Fq::from(4)]);
+
+		let sum = DomainSumcheckProver::calculate_poly_sum(&poly, &domain);
+		let (proof, _) = DomainSumcheckProver::prove(&poly, &domain, &sum).unwrap();
+		assert_eq!(DomainSumcheckVerifier::verify(&poly, &domain, &proof), Ok(()));
+
+		assert!(multiplicative_subgroup::<Fq>(3).is_none());
+	}
+
+	#[test]
+	fn test_boolean_domain_matches_hypercube_sum() {
+		let poly = sample_poly();
+		let domain = vec![Fq::from(0), Fq::from(1)];
+		let sum = DomainSumcheckProver::calculate_poly_sum(&poly, &domain);
+		assert_eq!(sum, MultiComposedSumcheckProver::calculate_poly_sum(&poly));
+
+		let (proof, _) = DomainSumcheckProver::prove(&poly, &domain, &sum).unwrap();
+		let (hypercube_proof, _) = MultiComposedSumcheckProver::prove(&poly, &sum).unwrap();
+		assert_eq!(proof.round_polys[0], hypercube_proof.round_polys[0]);
+		assert_eq!(MultiComposedSumcheckVerifier::verify(&poly, &hypercube_proof), Ok(()));
+	}
+
+	#[test]
+	fn test_domain_with_repeated_points_is_rejected() {
+		let poly = sample_poly();
+		let domain = vec![Fq::from(0), Fq::from(1), Fq::from(1)];
+		assert!(DomainSumcheckProver::prove(&poly, &domain, &Fq::from(0)).is_err());
+
+		let (proof, _) =
+			DomainSumcheckProver::prove(&poly, &[Fq::from(0), Fq::from(1)], &Fq::from(0)).unwrap();
+		assert_eq!(
+			DomainSumcheckVerifier::verify(&poly, &domain, &proof),
+			Err(SumcheckError::MalformedProof("Domain points must be distinct"))
+		);
+	}
+}
@@ -0,0 +1,46 @@
+use ark_ff::Field;
+use std::fmt::{self, Display};
+
+/// Why a sum-check verifier rejected a proof. Rounds are counted from zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SumcheckError<F: Field> {
+	/// `s(0) + s(1)` of a round polynomial, or its sum over the domain, does not match the claim
+	/// carried into that round.
+	RoundSumMismatch { round: usize, expected: F, got: F },
+	/// The proof has a different number of rounds than the polynomial has variables.
+	WrongRoundCount { expected: usize, got: usize },
+	/// A round polynomial has a higher degree than the verifier accepts.
+	DegreeTooHigh { round: usize },
+	/// The claim left after the last round does not match the polynomial at the challenges.
+	FinalEvaluationMismatch,
+	/// The sum the proof starts from is not the one the protocol requires, e.g. a non-zero sum
+	/// in a zero-check.
+	ClaimedSumMismatch { expected: F, got: F },
+	/// The proof is structurally broken, e.g. a round message with fewer than two evaluations.
+	MalformedProof(&'static str),
+}
+
+impl<F: Field> Display for SumcheckError<F> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			SumcheckError::RoundSumMismatch { round, expected, got } => {
+				write!(f, "round {round}: round polynomial sums to {got}, expected {expected}")
+			},
+			SumcheckError::WrongRoundCount { expected, got } => {
+				write!(f, "proof has {got} rounds, expected {expected}")
+			},
+			SumcheckError::DegreeTooHigh { round } => {
+				write!(f, "round {round}: round polynomial degree is too high")
+			},
+			SumcheckError::FinalEvaluationMismatch => {
+				write!(f, "final evaluation does not match the last round claim")
+			},
+			SumcheckError::ClaimedSumMismatch { expected, got } => {
+				write!(f, "claimed sum {got} does not match the required sum {expected}")
+			},
+			SumcheckError::MalformedProof(reason) => write!(f, "malformed proof: {reason}"),
+		}
+	}
+}
+
+impl<F: Field> std::error::Error for SumcheckError<F> {}
@@ -0,0 +1,647 @@
+//! Round-by-round sum-check prover and verifier.
+//!
+//! The non-interactive provers and verifiers in this crate drive these state machines with a
+//! Fiat–Shamir transcript. They can just as well be driven over a channel, with the verifier's
+//! challenges coming from a random source or chosen adversarially.
+//!
+//! The witness lives in a prime field `F` and the challenges in a field `E` over it, `F` itself
+//! unless the prover is built with [`SumcheckProverState::new_with_extension`]. Over a small
+//! field, e.g. Goldilocks, challenges from an extension keep the protocol sound while the
+//! witness tables stay in the base field.
+
+use crate::error::SumcheckError;
+use crate::multi_composedsumcheck::SubClaim;
+use crate::small_value::small_value_round_evaluations;
+use crate::util::{
+	convert_extension_field_to_byte, lagrange_evaluate, transform_round_poly_to_uni_poly,
+	univariate_degree,
+};
+use ark_ff::{Field, PrimeField};
+use polynomial::composed::interface::ComposedMultilinearInterface;
+use polynomial::composed::multilinear::ComposedMultiLinearPolynomial;
+use polynomial::interface::UnivariatePolynomialTrait;
+use polynomial::univariate_polynomial::univariate::UnivariatePolynomial;
+use rayon::prelude::*;
+use transcript::transcription::Transcript;
+
+/// The prover's message for one round: the round polynomial `s(X)` given by its evaluations at
+/// `0, 1, ..., d`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct RoundMessage<E: Field> {
+	pub evaluations: Vec<E>,
+}
+
+impl<E: Field> RoundMessage<E> {
+	pub fn new(evaluations: Vec<E>) -> Self {
+		Self { evaluations }
+	}
+
+	pub fn degree(&self) -> usize {
+		self.evaluations.len().saturating_sub(1)
+	}
+
+	pub fn evaluate(&self, point: E) -> Result<E, SumcheckError<E>> {
+		lagrange_evaluate(&self.evaluations, point)
+	}
+
+	/// `sum_{h in domain} s(h)`, what the verifier checks against the running claim.
+	pub fn domain_sum(&self, domain: &[E]) -> Result<E, SumcheckError<E>> {
+		domain.iter().map(|h| self.evaluate(*h)).sum()
+	}
+
+	/// The base field coefficients of every evaluation, big-endian. For a prime field these are
+	/// just the evaluations.
+	pub fn to_bytes(&self) -> Vec<u8> {
+		self.evaluations.iter().flat_map(convert_extension_field_to_byte).collect()
+	}
+}
+
+impl<F: PrimeField> RoundMessage<F> {
+	/// Builds the message for a round polynomial sent in coefficient form, using as few
+	/// evaluations as its degree allows.
+	pub fn from_univariate(poly: &UnivariatePolynomial<F>) -> Result<Self, &'static str> {
+		let degree = univariate_degree(poly)?.max(1);
+		Ok(Self { evaluations: (0..=degree).map(|i| poly.evaluate(F::from(i as u64))).collect() })
+	}
+
+	/// Like [`RoundMessage::from_univariate`], but fails for polynomials above `max_degree`
+	/// before evaluating them. The verifier uses this on round polynomials from a proof, whose
+	/// powers are chosen by the prover.
+	pub fn from_univariate_bounded(
+		poly: &UnivariatePolynomial<F>,
+		max_degree: usize,
+	) -> Result<Self, &'static str> {
+		if univariate_degree(poly)? > max_degree {
+			return Err("Round polynomial degree exceeds the bound");
+		}
+		Self::from_univariate(poly)
+	}
+
+	pub fn to_univariate(&self) -> UnivariatePolynomial<F> {
+		UnivariatePolynomial::interpolate(&transform_round_poly_to_uni_poly(&self.evaluations))
+	}
+}
+
+/// Where the verifier's challenge for a round comes from.
+pub trait ChallengeSource<E: Field> {
+	fn next_challenge(&mut self, message: &RoundMessage<E>) -> E;
+}
+
+/// Fiat–Shamir: absorb the round message and squeeze the challenge.
+pub fn fiat_shamir_challenge<E: Field>(
+	transcript: &mut Transcript,
+	message: &RoundMessage<E>,
+) -> E {
+	transcript.append(&message.to_bytes());
+	transcript.transform_challenge_to_extension_field::<E>()
+}
+
+impl<E: Field> ChallengeSource<E> for &mut Transcript {
+	fn next_challenge(&mut self, message: &RoundMessage<E>) -> E {
+		fiat_shamir_challenge(self, message)
+	}
+}
+
+/// Any closure can pick the challenges, e.g. a random number generator or an adversary.
+impl<E: Field, G: FnMut(&RoundMessage<E>) -> E> ChallengeSource<E> for G {
+	fn next_challenge(&mut self, message: &RoundMessage<E>) -> E {
+		self(message)
+	}
+}
+
+/// The tables the prover sums over.
+#[derive(Debug, Clone)]
+enum Tables<F: PrimeField, E: Field> {
+	/// The witness itself, read by the first round.
+	Base(Vec<ComposedMultiLinearPolynomial<F>>),
+	/// The tables of every product once a variable is bound to a challenge in `E`.
+	Lifted(Vec<Vec<Vec<E>>>),
+}
+
+/// Prover for `sum_i prod_j f_ij(x)` over the boolean hypercube, or over `H^n` for a domain `H`
+/// set with [`SumcheckProverState::with_domain`], one round at a time.
+///
+/// The first round runs on the base field witness. The first challenge lifts the folded tables
+/// into `E`, and every later round works there.
+///
+/// The products are evaluated in parallel and their round messages added in evaluation form.
+/// Large tables are also split across threads, both when evaluating and when folding.
+#[derive(Debug, Clone)]
+pub struct SumcheckProverState<F: PrimeField, E: Field<BasePrimeField = F> = F> {
+	tables: Tables<F, E>,
+	domain: Option<Vec<F>>,
+	small_value_first_round: bool,
+	degree: usize,
+	number_of_variables: usize,
+	round: usize,
+}
+
+impl<F: PrimeField> SumcheckProverState<F> {
+	pub fn new(poly: Vec<ComposedMultiLinearPolynomial<F>>) -> Self {
+		Self::new_with_extension(poly)
+	}
+}
+
+impl<F: PrimeField, E: Field<BasePrimeField = F>> SumcheckProverState<F, E> {
+	/// A prover for a base field witness answering challenges from `E`.
+	pub fn new_with_extension(poly: Vec<ComposedMultiLinearPolynomial<F>>) -> Self {
+		assert!(!poly.is_empty(), "Sum-check needs at least one composed polynomial");
+
+		let number_of_variables = poly[0].number_of_variables();
+		assert!(
+			poly.iter().all(|p| p.number_of_variables() == number_of_variables),
+			"Composed polynomials must have the same number of variables"
+		);
+		let degree = poly.iter().map(|p| p.max_degree()).max().unwrap_or(0);
+
+		Self {
+			tables: Tables::Base(poly),
+			domain: None,
+			small_value_first_round: false,
+			degree,
+			number_of_variables,
+			round: 0,
+		}
+	}
+
+	/// Computes the first round over machine integers for every product whose tables only hold
+	/// small values, see [`crate::small_value`]. The round messages are the same either way.
+	pub fn with_small_value_first_round(mut self) -> Self {
+		self.small_value_first_round = true;
+		self
+	}
+
+	/// Sums over `domain^n` instead of `{0, 1}^n`.
+	pub fn with_domain(mut self, domain: Vec<F>) -> Self {
+		self.domain = Some(domain);
+		self
+	}
+
+	pub fn number_of_variables(&self) -> usize {
+		self.number_of_variables
+	}
+
+	/// The degree of every round polynomial this prover sends.
+	pub fn degree(&self) -> usize {
+		self.degree
+	}
+
+	pub fn is_finished(&self) -> bool {
+		self.round == self.number_of_variables
+	}
+
+	/// Binds the previous round's variable to `challenge` and returns the next round polynomial.
+	/// The first round takes no challenge, every later round must get one.
+	pub fn next_round(&mut self, challenge: Option<E>) -> RoundMessage<E> {
+		assert!(!self.is_finished(), "All sum-check rounds have already been sent");
+
+		match challenge {
+			Some(r) => {
+				assert!(self.round > 0, "The first round does not take a challenge");
+				let lifted = match &self.tables {
+					Tables::Base(poly) => poly
+						.par_iter()
+						.map(|p| {
+							p.multilineal_polynomial
+								.par_iter()
+								.map(|table| {
+									fold_first_variable(
+										&table.evaluations,
+										r,
+										E::from_base_prime_field,
+									)
+								})
+								.collect()
+						})
+						.collect(),
+					Tables::Lifted(products) => products
+						.par_iter()
+						.map(|tables| {
+							tables
+								.par_iter()
+								.map(|table| fold_first_variable(table, r, |e| e))
+								.collect()
+						})
+						.collect(),
+				};
+				self.tables = Tables::Lifted(lifted);
+			},
+			None => assert!(self.round == 0, "Every round after the first needs a challenge"),
+		}
+
+		let degree = self.degree;
+		let term_evaluations: Vec<Vec<E>> = match &self.tables {
+			Tables::Base(poly) => {
+				let small_values = self.small_value_first_round && self.domain.is_none();
+				poly.par_iter()
+					.map(|p| {
+						let tables: Vec<&[F]> = p
+							.multilineal_polynomial
+							.iter()
+							.map(|t| t.evaluations.as_slice())
+							.collect();
+						let evaluations = match &self.domain {
+							Some(domain) => domain_round_evaluations(&tables, domain, degree),
+							None => small_values
+								.then(|| small_value_round_evaluations(p, degree))
+								.flatten()
+								.unwrap_or_else(|| round_evaluations(&tables, degree)),
+						};
+						evaluations.into_iter().map(E::from_base_prime_field).collect()
+					})
+					.collect()
+			},
+			Tables::Lifted(products) => {
+				let domain: Option<Vec<E>> = self
+					.domain
+					.as_ref()
+					.map(|domain| domain.iter().map(|h| E::from_base_prime_field(*h)).collect());
+				products
+					.par_iter()
+					.map(|tables| match &domain {
+						Some(domain) => domain_round_evaluations(tables, domain, degree),
+						None => round_evaluations(tables, degree),
+					})
+					.collect()
+			},
+		};
+
+		let mut evaluations = vec![E::zero(); degree + 1];
+		for term in term_evaluations {
+			evaluations.iter_mut().zip(term).for_each(|(evaluation, e)| *evaluation += e);
+		}
+
+		self.round += 1;
+		RoundMessage { evaluations }
+	}
+}
+
+/// Tables with fewer entries than this are folded and summed on the current thread.
+pub(crate) const PARALLEL_THRESHOLD: usize = 1 << 10;
+
+/// `sum_x prod_j f_j(t, x)` at `t = 0, ..., degree`, straight from the tables: the first variable
+/// is the top bit of the index, so `f_j(t, x) = low + t * (high - low)` with `low = f_j[x]` and
+/// `high = f_j[x + half]`.
+fn round_evaluations<K: Field, T: AsRef<[K]> + Sync>(tables: &[T], degree: usize) -> Vec<K> {
+	let half = tables[0].as_ref().len() / 2;
+
+	let accumulate = |mut sums: Vec<K>, x: usize| {
+		let mut values: Vec<K> = tables.iter().map(|table| table.as_ref()[x]).collect();
+		let steps: Vec<K> = tables
+			.iter()
+			.map(|table| table.as_ref()[x + half] - table.as_ref()[x])
+			.collect();
+		for sum in sums.iter_mut() {
+			*sum += values.iter().product::<K>();
+			values.iter_mut().zip(steps.iter()).for_each(|(value, step)| *value += step);
+		}
+		sums
+	};
+	let add = |mut a: Vec<K>, b: Vec<K>| {
+		a.iter_mut().zip(b).for_each(|(a, b)| *a += b);
+		a
+	};
+
+	if half < PARALLEL_THRESHOLD {
+		return (0..half).fold(vec![K::zero(); degree + 1], accumulate);
+	}
+	(0..half)
+		.into_par_iter()
+		.fold(|| vec![K::zero(); degree + 1], accumulate)
+		.reduce(|| vec![K::zero(); degree + 1], add)
+}
+
+/// Binds the first variable of a table to `r`, lifting the entries into the field of `r`. The
+/// same as `partial_evaluation(r, 0)` on the MLE of the table.
+pub(crate) fn fold_first_variable<T: Field, K: Field>(
+	evaluations: &[T],
+	r: K,
+	lift: impl Fn(T) -> K + Sync,
+) -> Vec<K> {
+	let half = evaluations.len() / 2;
+	let (low, high) = evaluations.split_at(half);
+	let fold = |(l, h): (&T, &T)| lift(*l) + r * lift(*h - l);
+	if half < PARALLEL_THRESHOLD {
+		low.iter().zip(high).map(fold).collect()
+	} else {
+		low.par_iter().zip(high).map(fold).collect()
+	}
+}
+
+/// The round polynomial for a sum over `domain^n`: `sum_{x in domain^(n-1)} prod_j f_j(t, x)` at
+/// `t = 0, ..., degree`.
+fn domain_round_evaluations<K: Field, T: AsRef<[K]>>(
+	tables: &[T],
+	domain: &[K],
+	degree: usize,
+) -> Vec<K> {
+	(0..=degree)
+		.map(|t| {
+			let t = K::from(t as u64);
+			let fixed: Vec<Vec<K>> = tables
+				.iter()
+				.map(|table| fold_first_variable(table.as_ref(), t, |e| e))
+				.collect();
+			sum_over_domain(&fixed, domain)
+		})
+		.collect()
+}
+
+/// `sum_{x in domain^n} prod_j f_j(x)`, fixing one variable to every point of `domain` at a time.
+fn sum_over_domain<K: Field>(tables: &[Vec<K>], domain: &[K]) -> K {
+	if tables[0].len() == 1 {
+		return tables.iter().map(|table| table[0]).product();
+	}
+	domain
+		.iter()
+		.map(|h| {
+			let fixed: Vec<Vec<K>> =
+				tables.iter().map(|table| fold_first_variable(table, *h, |e| e)).collect();
+			sum_over_domain(&fixed, domain)
+		})
+		.sum()
+}
+
+/// Verifier for a claimed sum over `number_of_variables` rounds, one round at a time.
+pub struct SumcheckVerifierState<E: Field, C: ChallengeSource<E>> {
+	claimed_sum: E,
+	number_of_variables: usize,
+	domain: Option<Vec<E>>,
+	degree_bounds: Vec<usize>,
+	challenges: Vec<E>,
+	challenge_source: C,
+}
+
+impl<E: Field, C: ChallengeSource<E>> SumcheckVerifierState<E, C> {
+	/// Rejects any round message of higher degree than `degree_bounds[round]`; without the bounds
+	/// a verifier would accept round polynomials of any degree, which is unsound.
+	pub fn new(
+		number_of_variables: usize,
+		sum: E,
+		degree_bounds: Vec<usize>,
+		challenge_source: C,
+	) -> Self {
+		assert_eq!(degree_bounds.len(), number_of_variables, "Need one degree bound per round");
+		Self {
+			claimed_sum: sum,
+			number_of_variables,
+			domain: None,
+			degree_bounds,
+			challenges: vec![],
+			challenge_source,
+		}
+	}
+
+	/// Checks `sum_{h in domain} s(h)` against the claim each round instead of `s(0) + s(1)`.
+	pub fn with_domain(mut self, domain: Vec<E>) -> Self {
+		self.domain = Some(domain);
+		self
+	}
+
+	pub fn is_finished(&self) -> bool {
+		self.challenges.len() == self.number_of_variables
+	}
+
+	/// Checks `s(0) + s(1)`, or the sum of `s` over the domain, against the running claim and answers with the round's challenge.
+	pub fn receive(&mut self, message: &RoundMessage<E>) -> Result<E, SumcheckError<E>> {
+		let round = self.challenges.len();
+		if self.is_finished() {
+			return Err(SumcheckError::WrongRoundCount {
+				expected: self.number_of_variables,
+				got: round + 1,
+			});
+		}
+		if message.evaluations.len() < 2 {
+			return Err(SumcheckError::MalformedProof(
+				"Round message needs at least two evaluations",
+			));
+		}
+		if message.degree() > self.degree_bounds[round] {
+			return Err(SumcheckError::DegreeTooHigh { round });
+		}
+
+		let round_sum = match &self.domain {
+			Some(domain) => message.domain_sum(domain)?,
+			None => message.evaluations[0] + message.evaluations[1],
+		};
+		if self.claimed_sum != round_sum {
+			return Err(SumcheckError::RoundSumMismatch {
+				round,
+				expected: self.claimed_sum,
+				got: round_sum,
+			});
+		}
+
+		let challenge = self.challenge_source.next_challenge(message);
+		self.claimed_sum = message.evaluate(challenge)?;
+		self.challenges.push(challenge);
+
+		Ok(challenge)
+	}
+
+	/// Returns the claim left for the final evaluation check once every round is done.
+	pub fn finalize(self) -> Result<SubClaim<E>, SumcheckError<E>> {
+		if !self.is_finished() {
+			return Err(SumcheckError::WrongRoundCount {
+				expected: self.number_of_variables,
+				got: self.challenges.len(),
+			});
+		}
+
+		Ok(SubClaim { sum: self.claimed_sum, challenges: self.challenges })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::multi_composedsumcheck::MultiComposedSumcheckProver;
+	use ark_ff::MontConfig;
+	use ark_ff::{Fp64, MontBackend};
+	use polynomial::multilinear::evaluation_form::MultiLinearPolynomialEvaluationForm;
+	use polynomial::multilinear::interface::MultiLinearPolynomialEvaluationFormTrait;
+
+	#[derive(MontConfig)]
+	#[modulus = "17"]
+	#[generator = "3"]
+	struct FqConfig;
+	type Fq = Fp64<MontBackend<FqConfig, 1>>;
+
+	fn sample_poly() -> Vec<ComposedMultiLinearPolynomial<Fq>> {
+		let to_mle = |v: Vec<u64>| {
+			MultiLinearPolynomialEvaluationForm::new(v.into_iter().map(Fq::from).collect())
+		};
+		let a = to_mle(vec![1, 4, 2, 8, 5, 7, 0, 3]);
+		let b = to_mle(vec![3, 3, 6, 1, 2, 9, 4, 4]);
+
+		vec![
+			ComposedMultiLinearPolynomial::new(vec![a.clone(), b.clone()]),
+			ComposedMultiLinearPolynomial::new(vec![a]),
+		]
+	}
+
+	#[test]
+	fn test_interactive_session_with_chosen_challenges() {
+		let poly = sample_poly();
+		let sum = MultiComposedSumcheckProver::calculate_poly_sum(&poly);
+
+		// the verifier picks its own challenges instead of hashing
+		let mut next = 2u64;
+		let chooser = |_: &RoundMessage<Fq>| {
+			next += 3;
+			Fq::from(next)
+		};
+
+		let mut prover = SumcheckProverState::new(poly.clone());
+		let mut verifier = SumcheckVerifierState::new(3, sum, vec![2; 3], chooser);
+
+		let mut challenge = None;
+		while !prover.is_finished() {
+			let message = prover.next_round(challenge);
+			challenge = Some(verifier.receive(&message).unwrap());
+		}
+
+		let sub_claim = verifier.finalize().unwrap();
+		assert_eq!(sub_claim.challenges, vec![Fq::from(5), Fq::from(8), Fq::from(11)]);
+
+		let evaluation: Fq = poly.iter().map(|p| p.evaluation(&sub_claim.challenges)).sum();
+		assert_eq!(evaluation, sub_claim.sum);
+	}
+
+	#[test]
+	fn test_verifier_rejects_dishonest_round() {
+		let poly = sample_poly();
+		let sum = MultiComposedSumcheckProver::calculate_poly_sum(&poly);
+
+		let mut prover = SumcheckProverState::new(poly);
+		let mut verifier =
+			SumcheckVerifierState::new(3, sum, vec![2; 3], |_: &RoundMessage<Fq>| Fq::from(7));
+
+		let mut message = prover.next_round(None);
+		message.evaluations[1] += Fq::from(1);
+		assert!(matches!(
+			verifier.receive(&message),
+			Err(SumcheckError::RoundSumMismatch { round: 0, .. })
+		));
+	}
+
+	#[test]
+	fn test_verifier_rejects_early_finalize_and_extra_rounds() {
+		let poly = sample_poly();
+		let sum = MultiComposedSumcheckProver::calculate_poly_sum(&poly);
+		let mut transcript = Transcript::new();
+
+		let mut prover = SumcheckProverState::new(poly);
+		let mut verifier = SumcheckVerifierState::new(1, sum, vec![2], &mut transcript);
+
+		let message = prover.next_round(None);
+		let challenge = verifier.receive(&message).unwrap();
+		let message = prover.next_round(Some(challenge));
+		assert_eq!(
+			verifier.receive(&message),
+			Err(SumcheckError::WrongRoundCount { expected: 1, got: 2 })
+		);
+
+		let verifier =
+			SumcheckVerifierState::new(3, sum, vec![2; 3], |_: &RoundMessage<Fq>| Fq::from(1));
+		assert_eq!(
+			verifier.finalize().unwrap_err(),
+			SumcheckError::WrongRoundCount { expected: 3, got: 0 }
+		);
+	}
+
+	#[test]
+	fn test_verifier_rejects_round_above_degree_bound() {
+		let mut verifier =
+			SumcheckVerifierState::new(2, Fq::from(3), vec![2, 1], |_: &RoundMessage<Fq>| {
+				Fq::from(2)
+			});
+
+		// s(X) = X^2 + 1 fits the first bound but a degree two message is too long for the second
+		let message = RoundMessage::new(vec![Fq::from(1), Fq::from(2), Fq::from(5)]);
+		assert_eq!(verifier.receive(&message), Ok(Fq::from(2)));
+		assert_eq!(
+			verifier.receive(&RoundMessage::new(vec![Fq::from(1), Fq::from(4), Fq::from(9)])),
+			Err(SumcheckError::DegreeTooHigh { round: 1 })
+		);
+
+		let oversized = UnivariatePolynomial::new(vec![Fq::from(1), Fq::from(16)]);
+		assert!(RoundMessage::from_univariate_bounded(&oversized, 2).is_err());
+	}
+
+	#[test]
+	fn test_verifier_rejects_more_evaluations_than_field_points() {
+		// 18 evaluations over F_17 repeat the point 0, so the Lagrange basis does not exist
+		let message = RoundMessage::new(vec![Fq::from(1); 18]);
+		let mut verifier =
+			SumcheckVerifierState::new(1, Fq::from(2), vec![17], |_: &RoundMessage<Fq>| {
+				Fq::from(5)
+			});
+
+		assert!(matches!(verifier.receive(&message), Err(SumcheckError::MalformedProof(_))));
+		assert!(message.evaluate(Fq::from(5)).is_err());
+	}
+
+	#[test]
+	fn test_small_value_first_round_sends_the_same_messages() {
+		let poly = sample_poly();
+		let challenges = [Fq::from(3), Fq::from(8)];
+
+		let mut plain = SumcheckProverState::new(poly.clone());
+		let mut small = SumcheckProverState::new(poly).with_small_value_first_round();
+		assert_eq!(small.next_round(None), plain.next_round(None));
+		for challenge in challenges {
+			assert_eq!(small.next_round(Some(challenge)), plain.next_round(Some(challenge)));
+		}
+	}
+
+	#[test]
+	fn test_parallel_rounds_match_partial_evaluation() {
+		// large enough for the tables to be split across threads
+		let to_mle = |seed: u64| {
+			MultiLinearPolynomialEvaluationForm::new(
+				(0..1u64 << 12).map(|i| Fq::from(i * i + seed * i + 1)).collect(),
+			)
+		};
+		let poly = vec![
+			ComposedMultiLinearPolynomial::new(vec![to_mle(1), to_mle(2), to_mle(3)]),
+			ComposedMultiLinearPolynomial::new(vec![to_mle(4)]),
+		];
+
+		let mut expected_poly = poly.clone();
+		let mut prover = SumcheckProverState::new(poly);
+		let mut challenge = None;
+		for r in [2, 9, 5, 14] {
+			let message = prover.next_round(challenge);
+
+			let expected: Vec<Fq> = (0..=3u64)
+				.map(|t| {
+					expected_poly
+						.iter()
+						.map(|p| {
+							p.partial_evaluation(Fq::from(t), 0)
+								.elementwise_product()
+								.iter()
+								.sum::<Fq>()
+						})
+						.sum()
+				})
+				.collect();
+			assert_eq!(message.evaluations, expected);
+
+			challenge = Some(Fq::from(r));
+			expected_poly =
+				expected_poly.iter().map(|p| p.partial_evaluation(Fq::from(r), 0)).collect();
+		}
+	}
+
+	#[test]
+	fn test_round_message_univariate_conversion() {
+		// s(X) = 3 + 2X + X^2
+		let message = RoundMessage::new(vec![Fq::from(3), Fq::from(6), Fq::from(11)]);
+		let univariate = message.to_univariate();
+
+		assert_eq!(Ok(univariate.evaluate(Fq::from(5))), message.evaluate(Fq::from(5)));
+		assert_eq!(RoundMessage::from_univariate(&univariate).unwrap(), message);
+	}
+}
@@ -3,3 +3,13 @@ pub mod interface;
 pub mod util;
 pub mod composedsumcheck;
 pub mod multi_composedsumcheck;
+pub mod zerocheck;
+pub mod productcheck;
+pub mod serialization;
+pub mod interactive;
+pub mod error;
+pub mod oracle;
+pub mod statement;
+pub mod domain_sumcheck;
+pub mod multivariate_sumcheck;
+pub mod small_value;
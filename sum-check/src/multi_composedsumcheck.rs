@@ -1,6 +1,14 @@
 use super::composedsumcheck::ComposedSumcheck;
-use crate::util::{convert_field_to_byte, transform_round_poly_to_uni_poly, vec_to_bytes};
-use ark_ff::{PrimeField};
+use crate::error::SumcheckError;
+use crate::interactive::{
+    fiat_shamir_challenge, RoundMessage, SumcheckProverState, SumcheckVerifierState,
+};
+use crate::oracle::EvaluationOracle;
+use crate::statement::{
+    SumcheckStatement, EXTENSION_SUMCHECK_PROTOCOL, MULTI_COMPOSED_SUMCHECK_PROTOCOL,
+};
+use crate::util::evaluate_mle_at_extension_point;
+use ark_ff::{Field, PrimeField};
 use polynomial::composed::interface::ComposedMultilinearInterface;
 use polynomial::composed::multilinear::ComposedMultiLinearPolynomial;
 use polynomial::interface::UnivariatePolynomialTrait;
@@ -15,8 +23,19 @@ pub struct ComposedSumcheckProof<F: PrimeField> {
     pub sum: F,
 }
 
+/// Sum-check proof whose round messages and challenges live in an extension field `E` of the
+/// witness field `F`.
+///
+/// Each round polynomial is sent as its evaluations at `0, 1, ..., d`. The claimed sum is a sum of
+/// base field values, so it stays in the base field `F`.
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct ExtensionSumcheckProof<F: PrimeField, E: Field<BasePrimeField = F>> {
+    pub round_polys: Vec<Vec<E>>,
+    pub sum: F,
+}
+
 #[derive(Debug)]
-pub struct SubClaim<F: PrimeField> {
+pub struct SubClaim<F: Field> {
     pub sum: F,
     pub challenges: Vec<F>,
 }
@@ -55,63 +74,69 @@ impl MultiComposedSumcheckProver {
         poly: &Vec<ComposedMultiLinearPolynomial<F>>,
         sum: &F,
     ) -> Result<(ComposedSumcheckProof<F>, Vec<F>), &'static str> {
-        let mut transcript = Transcript::new();
-        transcript.append(&composed_mle_to_bytes(&poly));
-        MultiComposedSumcheckProver::prove_internal(&poly, &sum, &mut transcript)
+        MultiComposedSumcheckProver::prove_with_transcript(poly, sum, &mut Transcript::new())
     }
 
-    pub fn prove_partial<F: PrimeField>(
-        poly: &Vec<ComposedMultiLinearPolynomial<F>>,
+    /// Like [`MultiComposedSumcheckProver::prove`], continuing a transcript owned by an outer
+    /// protocol. The statement carries a digest of `poly`.
+    pub fn prove_with_transcript<F: PrimeField>(
+        poly: &[ComposedMultiLinearPolynomial<F>],
         sum: &F,
+        transcript: &mut Transcript,
     ) -> Result<(ComposedSumcheckProof<F>, Vec<F>), &'static str> {
-        let mut transcript = Transcript::new();
-        MultiComposedSumcheckProver::prove_internal(&poly, &sum, &mut transcript)
+        let statement = SumcheckStatement::for_oracle(MULTI_COMPOSED_SUMCHECK_PROTOCOL, poly, *sum);
+        MultiComposedSumcheckProver::prove_statement(poly, &statement, transcript)
     }
 
+    /// Proves `sum` for `poly` on a transcript the caller may already have written to. The
+    /// statement header carries the shape of `poly` but no digest of it.
     pub fn prove_internal<F: PrimeField>(
         poly: &Vec<ComposedMultiLinearPolynomial<F>>,
         sum: &F,
         transcript: &mut Transcript,
     ) -> Result<(ComposedSumcheckProof<F>, Vec<F>), &'static str> {
-        // append the sum to the transcript
-        transcript.append(&convert_field_to_byte(sum));
+        if poly.is_empty() {
+            return Err("Sum-check needs at least one composed polynomial");
+        }
 
-        let mut current_poly = poly.clone();
-        let mut round_polys = vec![];
-        let mut challenges: Vec<F> = vec![];
+        let statement = SumcheckStatement::new(
+            MULTI_COMPOSED_SUMCHECK_PROTOCOL,
+            poly.as_slice().number_of_variables(),
+            poly.as_slice().degree(),
+            *sum,
+        );
+        MultiComposedSumcheckProver::prove_statement(poly, &statement, transcript)
+    }
 
-        for _ in 0..poly[0].number_of_variables() {
-            let mut round_poly = UnivariatePolynomial::zero();
+    /// Absorbs `statement` and proves it for `poly`.
+    pub fn prove_statement<F: PrimeField>(
+        poly: &[ComposedMultiLinearPolynomial<F>],
+        statement: &SumcheckStatement<F>,
+        transcript: &mut Transcript,
+    ) -> Result<(ComposedSumcheckProof<F>, Vec<F>), &'static str> {
+        if poly.is_empty() {
+            return Err("Sum-check needs at least one composed polynomial");
+        }
+        if poly.number_of_variables() != statement.number_of_variables {
+            return Err("Statement has a different number of variables than the polynomial");
+        }
+        if poly.degree() > statement.degree {
+            return Err("Polynomial degree exceeds the statement's degree bound");
+        }
 
-            for p in current_poly.iter() {
-                let mut round_i_poly_vec = Vec::new();
-                for i in 0..=p.max_degree() {
-                    let round: F = p
-                        .partial_evaluation(F::from(i as u32), 0)
-                        .elementwise_product()
-                        .iter()
-                        .sum::<F>();
+        statement.absorb(transcript);
 
-                    round_i_poly_vec.push(round);
-                }
+        let mut prover = SumcheckProverState::new(poly.to_vec()).with_small_value_first_round();
+        let mut round_polys = vec![];
+        let mut challenges: Vec<F> = vec![];
 
-                let round_i_poly = UnivariatePolynomial::interpolate(
-                    &transform_round_poly_to_uni_poly(&round_i_poly_vec),
-                );
-                round_poly = round_poly + round_i_poly;
-            }
+        while !prover.is_finished() {
+            let round_poly = prover.next_round(challenges.last().copied()).to_univariate();
 
-            transcript.append(&round_poly.to_bytes());
+            // absorb the message exactly as the verifier rebuilds it from the proof
+            let message = RoundMessage::from_univariate(&round_poly)?;
             //get the random r
-            let random_r: F = transcript.transform_challenge_to_field::<F>();
-
-            let mut new_poly = Vec::new();
-
-            for i in 0..current_poly.len() {
-                new_poly.push(current_poly[i].partial_evaluation(random_r, 0));
-            }
-
-            current_poly = new_poly;
+            let random_r: F = fiat_shamir_challenge(transcript, &message);
 
             challenges.push(random_r);
             round_polys.push(round_poly);
@@ -119,11 +144,67 @@ impl MultiComposedSumcheckProver {
         Ok((
             ComposedSumcheckProof {
                 round_polys,
-                sum: *sum,
+                sum: statement.sum,
             },
             challenges,
         ))
     }
+
+    /// Proves `sum` for the base field `poly` with challenges from the extension field `E`. The
+    /// first round runs entirely in `F`, later rounds on tables lifted into `E`.
+    pub fn prove_over_extension<F: PrimeField, E: Field<BasePrimeField = F>>(
+        poly: &[ComposedMultiLinearPolynomial<F>],
+        sum: &F,
+    ) -> Result<(ExtensionSumcheckProof<F, E>, Vec<E>), &'static str> {
+        MultiComposedSumcheckProver::prove_over_extension_with_transcript(
+            poly,
+            sum,
+            &mut Transcript::new(),
+        )
+    }
+
+    /// Like [`MultiComposedSumcheckProver::prove_over_extension`], continuing a transcript owned
+    /// by an outer protocol.
+    pub fn prove_over_extension_with_transcript<F: PrimeField, E: Field<BasePrimeField = F>>(
+        poly: &[ComposedMultiLinearPolynomial<F>],
+        sum: &F,
+        transcript: &mut Transcript,
+    ) -> Result<(ExtensionSumcheckProof<F, E>, Vec<E>), &'static str> {
+        let statement = SumcheckStatement::for_oracle(EXTENSION_SUMCHECK_PROTOCOL, poly, *sum);
+        MultiComposedSumcheckProver::prove_statement_over_extension(poly, &statement, transcript)
+    }
+
+    /// Like [`MultiComposedSumcheckProver::prove_statement`], with challenges from `E`.
+    pub fn prove_statement_over_extension<F: PrimeField, E: Field<BasePrimeField = F>>(
+        poly: &[ComposedMultiLinearPolynomial<F>],
+        statement: &SumcheckStatement<F>,
+        transcript: &mut Transcript,
+    ) -> Result<(ExtensionSumcheckProof<F, E>, Vec<E>), &'static str> {
+        if poly.is_empty() {
+            return Err("Sum-check needs at least one composed polynomial");
+        }
+        if poly.number_of_variables() != statement.number_of_variables {
+            return Err("Statement has a different number of variables than the polynomial");
+        }
+        if poly.degree() > statement.degree {
+            return Err("Polynomial degree exceeds the statement's degree bound");
+        }
+
+        statement.absorb(transcript);
+
+        let mut prover = SumcheckProverState::<F, E>::new_with_extension(poly.to_vec())
+            .with_small_value_first_round();
+        let mut round_polys = vec![];
+        let mut challenges: Vec<E> = vec![];
+
+        while !prover.is_finished() {
+            let message = prover.next_round(challenges.last().copied());
+            challenges.push(fiat_shamir_challenge(transcript, &message));
+            round_polys.push(message.evaluations);
+        }
+
+        Ok((ExtensionSumcheckProof { round_polys, sum: statement.sum }, challenges))
+    }
 }
 
 pub struct MultiComposedSumcheckVerifier {}
@@ -132,65 +213,158 @@ impl MultiComposedSumcheckVerifier {
     pub fn verify<F: PrimeField>(
         poly: &Vec<ComposedMultiLinearPolynomial<F>>,
         proof: &ComposedSumcheckProof<F>,
-    ) -> Result<bool, &'static str> {
-        let mut transcript = Transcript::new();
+    ) -> Result<(), SumcheckError<F>> {
+        Self::verify_with_transcript(poly, proof, &mut Transcript::new())
+    }
 
-        transcript.append(&composed_mle_to_bytes(&poly));
-        let sub_claim = Self::verify_internal(&proof, &mut transcript)?;
+    pub fn verify_with_transcript<F: PrimeField>(
+        poly: &[ComposedMultiLinearPolynomial<F>],
+        proof: &ComposedSumcheckProof<F>,
+        transcript: &mut Transcript,
+    ) -> Result<(), SumcheckError<F>> {
+        let statement =
+            SumcheckStatement::for_oracle(MULTI_COMPOSED_SUMCHECK_PROTOCOL, poly, proof.sum);
+        let sub_claim = Self::verify_statement(proof, &statement, transcript)?;
 
-        let mut poly_pe_sum = F::zero();
-        for p in poly.iter() {
-            poly_pe_sum += p.evaluation(&sub_claim.challenges.as_slice())
+        if poly.evaluate(&sub_claim.challenges)? != sub_claim.sum {
+            return Err(SumcheckError::FinalEvaluationMismatch);
         }
 
-        Ok(poly_pe_sum == sub_claim.sum)
+        Ok(())
     }
-    pub fn verify_partial<F: PrimeField>(
+    /// Counterpart of [`MultiComposedSumcheckProver::prove_internal`]: the verifier supplies the
+    /// shape of the polynomial, the claimed sum comes from the proof.
+    pub fn verify_internal<F: PrimeField>(
         proof: &ComposedSumcheckProof<F>,
-    ) -> Result<SubClaim<F>, &'static str> {
-        let mut transcript = Transcript::new();
-        let sub_claim = Self::verify_internal(&proof, &mut transcript);
-        Ok(sub_claim)?
+        number_of_variables: usize,
+        degree: usize,
+        transcript: &mut Transcript,
+    ) -> Result<SubClaim<F>, SumcheckError<F>> {
+        let statement = SumcheckStatement::new(
+            MULTI_COMPOSED_SUMCHECK_PROTOCOL,
+            number_of_variables,
+            degree,
+            proof.sum,
+        );
+        Self::verify_statement(proof, &statement, transcript)
     }
 
-    pub fn verify_internal<F: PrimeField>(
+    /// Absorbs `statement` and checks the rounds of `proof` against it, returning the claim left
+    /// for the final evaluation.
+    pub fn verify_statement<F: PrimeField>(
         proof: &ComposedSumcheckProof<F>,
+        statement: &SumcheckStatement<F>,
         transcript: &mut Transcript,
-    ) -> Result<SubClaim<F>, &'static str> {
-        // append the sum to the transcript
-        transcript.append(&convert_field_to_byte(&proof.sum));
+    ) -> Result<SubClaim<F>, SumcheckError<F>> {
+        if proof.sum != statement.sum {
+            return Err(SumcheckError::ClaimedSumMismatch { expected: statement.sum, got: proof.sum });
+        }
+        if proof.round_polys.len() != statement.number_of_variables {
+            return Err(SumcheckError::WrongRoundCount {
+                expected: statement.number_of_variables,
+                got: proof.round_polys.len(),
+            });
+        }
 
-        let mut claimed_sum = proof.sum;
-        let mut challenges: Vec<F> = vec![];
+        statement.absorb(transcript);
+
+        let mut verifier =
+            SumcheckVerifierState::new(
+                statement.number_of_variables,
+                statement.sum,
+                statement.degree_bounds(),
+                &mut *transcript,
+            );
+
+        for (round, round_poly) in proof.round_polys.iter().enumerate() {
+            // the powers are the prover's, check them before evaluating anything
+            let message =
+                RoundMessage::from_univariate_bounded(round_poly, statement.degree_bound(round))
+                    .map_err(|_| SumcheckError::DegreeTooHigh { round })?;
+            verifier.receive(&message)?;
+        }
 
-        for round_poly in proof.round_polys.iter() {
-            transcript.append(&round_poly.to_bytes());
-            // genrate the challenge for this round
-            let challenge: F = transcript.transform_challenge_to_field::<F>();
-            challenges.push(challenge);
+        verifier.finalize()
+    }
+
+    /// Counterpart of [`MultiComposedSumcheckProver::prove_over_extension`], evaluating `poly` at
+    /// the extension field challenges for the final check.
+    pub fn verify_over_extension<F: PrimeField, E: Field<BasePrimeField = F>>(
+        poly: &[ComposedMultiLinearPolynomial<F>],
+        proof: &ExtensionSumcheckProof<F, E>,
+    ) -> Result<(), SumcheckError<E>> {
+        Self::verify_over_extension_with_transcript(poly, proof, &mut Transcript::new())
+    }
+
+    /// Like [`MultiComposedSumcheckVerifier::verify_over_extension`], continuing a transcript
+    /// owned by an outer protocol.
+    pub fn verify_over_extension_with_transcript<F: PrimeField, E: Field<BasePrimeField = F>>(
+        poly: &[ComposedMultiLinearPolynomial<F>],
+        proof: &ExtensionSumcheckProof<F, E>,
+        transcript: &mut Transcript,
+    ) -> Result<(), SumcheckError<E>> {
+        let statement =
+            SumcheckStatement::for_oracle(EXTENSION_SUMCHECK_PROTOCOL, poly, proof.sum);
+        let sub_claim = Self::verify_statement_over_extension(proof, &statement, transcript)?;
+
+        let evaluation: E = poly
+            .iter()
+            .map(|p| {
+                p.multilineal_polynomial
+                    .iter()
+                    .map(|mle| evaluate_mle_at_extension_point(mle, &sub_claim.challenges))
+                    .product::<E>()
+            })
+            .sum();
+        if evaluation != sub_claim.sum {
+            return Err(SumcheckError::FinalEvaluationMismatch);
+        }
+
+        Ok(())
+    }
 
-            let eval_p0_p1 = round_poly.evaluate(F::zero()) + round_poly.evaluate(F::one());
+    /// Like [`MultiComposedSumcheckVerifier::verify_statement`], with challenges from `E`.
+    pub fn verify_statement_over_extension<F: PrimeField, E: Field<BasePrimeField = F>>(
+        proof: &ExtensionSumcheckProof<F, E>,
+        statement: &SumcheckStatement<F>,
+        transcript: &mut Transcript,
+    ) -> Result<SubClaim<E>, SumcheckError<E>> {
+        if proof.sum != statement.sum {
+            return Err(SumcheckError::ClaimedSumMismatch {
+                expected: E::from_base_prime_field(statement.sum),
+                got: E::from_base_prime_field(proof.sum),
+            });
+        }
+        if proof.round_polys.len() != statement.number_of_variables {
+            return Err(SumcheckError::WrongRoundCount {
+                expected: statement.number_of_variables,
+                got: proof.round_polys.len(),
+            });
+        }
 
-            if claimed_sum != eval_p0_p1 {
-                return Err("Verification failed");
-            }
+        statement.absorb(transcript);
 
-            // update the sum
-            claimed_sum = round_poly.evaluate(challenge);
+        let sum = E::from_base_prime_field(statement.sum);
+        let mut verifier = SumcheckVerifierState::new(
+            statement.number_of_variables,
+            sum,
+            statement.degree_bounds(),
+            transcript,
+        );
+        for round_poly in proof.round_polys.iter() {
+            verifier.receive(&RoundMessage::new(round_poly.clone()))?;
         }
 
-        Ok(SubClaim {
-            sum: claimed_sum,
-            challenges,
-        })
+        verifier.finalize()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ark_ff::fields::{Fp2, Fp2Config};
     use ark_ff::MontConfig;
-    use ark_ff::{Fp64, MontBackend};
+    use ark_ff::{Fp64, MontBackend, MontFp};
     use polynomial::multilinear::evaluation_form::MultiLinearPolynomialEvaluationForm;
     use polynomial::multilinear::interface::MultiLinearPolynomialEvaluationFormTrait;
 
@@ -200,6 +374,15 @@ mod tests {
     struct FqConfig;
     type Fq = Fp64<MontBackend<FqConfig, 1>>;
 
+    // F_17[u] / (u^2 - 3), 3 is a quadratic non-residue mod 17
+    struct Fq2Config;
+    impl Fp2Config for Fq2Config {
+        type Fp = Fq;
+        const NONRESIDUE: Fq = MontFp!("3");
+        const FROBENIUS_COEFF_FP2_C1: &'static [Fq] = &[MontFp!("1"), MontFp!("-1")];
+    }
+    type Fq2 = Fp2<Fq2Config>;
+
     #[test]
     fn test_sum_calculation() {
         let mle1 = MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(0), Fq::from(1), Fq::from(2), Fq::from(3)]);
@@ -233,8 +416,8 @@ mod tests {
 
         let sum = MultiComposedSumcheckProver::calculate_poly_sum(&multi_composed);
         let (proof, _) = MultiComposedSumcheckProver::prove(&multi_composed, &sum).unwrap();
-        let verify = MultiComposedSumcheckVerifier::verify(&multi_composed, &proof).unwrap();
-        assert!(verify);
+        let verify = MultiComposedSumcheckVerifier::verify(&multi_composed, &proof);
+        assert_eq!(verify, Ok(()));
     }
 
     #[test]
@@ -249,8 +432,8 @@ mod tests {
         let multi_composed = vec![composed_1, composed_2, composed_3];
         let sum = MultiComposedSumcheckProver::calculate_poly_sum(&multi_composed);
         let (proof, _) = MultiComposedSumcheckProver::prove(&multi_composed, &sum).unwrap();
-        let verify = MultiComposedSumcheckVerifier::verify(&multi_composed, &proof).unwrap();
-        assert!(verify);
+        let verify = MultiComposedSumcheckVerifier::verify(&multi_composed, &proof);
+        assert_eq!(verify, Ok(()));
     }
 
     #[test]
@@ -264,8 +447,8 @@ mod tests {
         let multi_composed = vec![composed_1, composed_2];
         let sum = MultiComposedSumcheckProver::calculate_poly_sum(&multi_composed);
         let (proof, _) = MultiComposedSumcheckProver::prove(&multi_composed, &sum).unwrap();
-        let verify = MultiComposedSumcheckVerifier::verify(&multi_composed, &proof).unwrap();
-        assert!(verify);
+        let verify = MultiComposedSumcheckVerifier::verify(&multi_composed, &proof);
+        assert_eq!(verify, Ok(()));
     }
 
     #[test]
@@ -310,7 +493,288 @@ mod tests {
         let sum = MultiComposedSumcheckProver::calculate_poly_sum(&multi_composed);
 
         let (proof, _) = MultiComposedSumcheckProver::prove(&multi_composed, &sum).unwrap();
-        let verify = MultiComposedSumcheckVerifier::verify(&multi_composed, &proof).unwrap();
-        assert!(verify);
+        let verify = MultiComposedSumcheckVerifier::verify(&multi_composed, &proof);
+        assert_eq!(verify, Ok(()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_multi_composed_sumcheck_reports_final_evaluation_mismatch() {
+        let poly1 = MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(0), Fq::from(0), Fq::from(0), Fq::from(2)]);
+        let poly2 = MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(0), Fq::from(3), Fq::from(0), Fq::from(3)]);
+        let poly3 = MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(1), Fq::from(2), Fq::from(0), Fq::from(3)]);
+
+        let proved = vec![
+            ComposedMultiLinearPolynomial::new(vec![poly1.clone()]),
+            ComposedMultiLinearPolynomial::new(vec![poly2]),
+        ];
+        // same sum over the hypercube, but a different polynomial
+        let claimed = vec![
+            ComposedMultiLinearPolynomial::new(vec![poly1]),
+            ComposedMultiLinearPolynomial::new(vec![poly3]),
+        ];
+        let sum = MultiComposedSumcheckProver::calculate_poly_sum(&proved);
+        assert_eq!(sum, MultiComposedSumcheckProver::calculate_poly_sum(&claimed));
+
+        // the prover runs on `proved` but commits to the statement about `claimed`
+        let statement =
+            SumcheckStatement::for_oracle(MULTI_COMPOSED_SUMCHECK_PROTOCOL, claimed.as_slice(), sum);
+        let (proof, _) = MultiComposedSumcheckProver::prove_statement(
+            &proved,
+            &statement,
+            &mut Transcript::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            MultiComposedSumcheckVerifier::verify(&claimed, &proof),
+            Err(SumcheckError::FinalEvaluationMismatch)
+        );
+
+        let mut short_proof = proof.clone();
+        short_proof.round_polys.pop();
+        assert_eq!(
+            MultiComposedSumcheckVerifier::verify(&claimed, &short_proof),
+            Err(SumcheckError::WrongRoundCount { expected: 2, got: 1 })
+        );
+    }
+
+    #[test]
+    fn test_forged_sum_is_rejected() {
+        let poly1 = MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(0), Fq::from(0), Fq::from(0), Fq::from(2)]);
+        let poly2 = MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(0), Fq::from(3), Fq::from(0), Fq::from(3)]);
+        let multi_composed = vec![
+            ComposedMultiLinearPolynomial::new(vec![poly1, poly2.clone()]),
+            ComposedMultiLinearPolynomial::new(vec![poly2]),
+        ];
+        let sum = MultiComposedSumcheckProver::calculate_poly_sum(&multi_composed);
+        let (proof, _) = MultiComposedSumcheckProver::prove(&multi_composed, &sum).unwrap();
+
+        // claim sum + 2 and shift the first round polynomial by one so s(0) + s(1) still matches
+        let mut forged = proof.clone();
+        forged.sum = sum + Fq::from(2);
+        forged.round_polys[0] =
+            forged.round_polys[0].clone() + UnivariatePolynomial::new(vec![Fq::from(1), Fq::from(0)]);
+
+        assert!(MultiComposedSumcheckVerifier::verify(&multi_composed, &forged).is_err());
+
+        // the forged sum changes the statement, so it also changes every challenge
+        let (_, honest_challenges) =
+            MultiComposedSumcheckProver::prove_internal(&multi_composed, &sum, &mut Transcript::new()).unwrap();
+        let mut transcript = Transcript::new();
+        SumcheckStatement::new(MULTI_COMPOSED_SUMCHECK_PROTOCOL, 2, 2, forged.sum)
+            .absorb(&mut transcript);
+        let message = RoundMessage::from_univariate(&forged.round_polys[0]).unwrap();
+        assert_ne!(fiat_shamir_challenge(&mut transcript, &message), honest_challenges[0]);
+    }
+
+    #[test]
+    fn test_prover_and_verifier_agree_on_commitment_digest() {
+        let poly1 = MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(1), Fq::from(4), Fq::from(2), Fq::from(2)]);
+        let poly2 = MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(0), Fq::from(3), Fq::from(5), Fq::from(3)]);
+        let multi_composed = vec![ComposedMultiLinearPolynomial::new(vec![poly1, poly2])];
+        let sum = MultiComposedSumcheckProver::calculate_poly_sum(&multi_composed);
+
+        // stands in for a commitment, it has nothing to do with the polynomial's bytes
+        let commitment = b"commitment to the polynomial".to_vec();
+        let statement = SumcheckStatement::for_commitment(
+            MULTI_COMPOSED_SUMCHECK_PROTOCOL,
+            2,
+            2,
+            sum,
+            commitment.clone(),
+        );
+        let (proof, challenges) =
+            MultiComposedSumcheckProver::prove_statement(&multi_composed, &statement, &mut Transcript::new())
+                .unwrap();
+
+        let sub_claim =
+            MultiComposedSumcheckVerifier::verify_statement(&proof, &statement, &mut Transcript::new()).unwrap();
+        assert_eq!(sub_claim.challenges, challenges);
+        assert_eq!(multi_composed.as_slice().evaluate(&challenges), Ok(sub_claim.sum));
+
+        // a verifier holding another commitment derives other challenges
+        let mut other_commitment = commitment;
+        other_commitment[0] ^= 1;
+        let other = SumcheckStatement::for_commitment(MULTI_COMPOSED_SUMCHECK_PROTOCOL, 2, 2, sum, other_commitment);
+        let other_claim = MultiComposedSumcheckVerifier::verify_statement(&proof, &other, &mut Transcript::new());
+        assert!(other_claim.map_or(true, |claim| claim.challenges != challenges));
+    }
+
+    #[test]
+    fn test_statement_binds_degree_bound() {
+        let poly1 = MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(1), Fq::from(4), Fq::from(2), Fq::from(2)]);
+        let poly2 = MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(0), Fq::from(3), Fq::from(5), Fq::from(3)]);
+        let multi_composed = vec![ComposedMultiLinearPolynomial::new(vec![poly1, poly2])];
+        let sum = MultiComposedSumcheckProver::calculate_poly_sum(&multi_composed);
+        let (proof, _) = MultiComposedSumcheckProver::prove_internal(&multi_composed, &sum, &mut Transcript::new()).unwrap();
+
+        assert!(MultiComposedSumcheckVerifier::verify_internal(&proof, 2, 2, &mut Transcript::new()).is_ok());
+        assert!(matches!(
+            MultiComposedSumcheckVerifier::verify_internal(&proof, 2, 3, &mut Transcript::new()),
+            Err(SumcheckError::RoundSumMismatch { round: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_oversized_round_polynomial_is_rejected() {
+        let poly1 = MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(1), Fq::from(4), Fq::from(2), Fq::from(2)]);
+        let poly2 = MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(0), Fq::from(3), Fq::from(5), Fq::from(3)]);
+        let multi_composed = vec![ComposedMultiLinearPolynomial::new(vec![poly1, poly2])];
+        let sum = MultiComposedSumcheckProver::calculate_poly_sum(&multi_composed);
+        let (proof, _) = MultiComposedSumcheckProver::prove(&multi_composed, &sum).unwrap();
+
+        // X^16 - X vanishes on 0 and 1, so the round sum still matches but the degree does not
+        let mut oversized = proof.clone();
+        oversized.round_polys[1] = oversized.round_polys[1].clone()
+            + UnivariatePolynomial::new(vec![-Fq::from(1), Fq::from(1), Fq::from(1), Fq::from(16)]);
+        assert_eq!(
+            MultiComposedSumcheckVerifier::verify(&multi_composed, &oversized),
+            Err(SumcheckError::DegreeTooHigh { round: 1 })
+        );
+        assert_eq!(
+            MultiComposedSumcheckVerifier::verify_internal(&oversized, 2, 2, &mut Transcript::new()).unwrap_err(),
+            SumcheckError::DegreeTooHigh { round: 1 }
+        );
+    }
+
+    #[test]
+    fn test_sumchecks_chained_on_one_transcript() {
+        let poly1 = MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(1), Fq::from(4), Fq::from(2), Fq::from(2)]);
+        let poly2 = MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(0), Fq::from(3), Fq::from(5), Fq::from(3)]);
+        let first = vec![ComposedMultiLinearPolynomial::new(vec![poly1.clone(), poly2.clone()])];
+        let second = vec![ComposedMultiLinearPolynomial::new(vec![poly2]), ComposedMultiLinearPolynomial::new(vec![poly1])];
+        let first_sum = MultiComposedSumcheckProver::calculate_poly_sum(&first);
+        let second_sum = MultiComposedSumcheckProver::calculate_poly_sum(&second);
+
+        let mut prover_transcript = Transcript::new();
+        prover_transcript.append(b"outer protocol");
+        let (first_proof, _) =
+            MultiComposedSumcheckProver::prove_with_transcript(&first, &first_sum, &mut prover_transcript).unwrap();
+        let (second_proof, _) =
+            MultiComposedSumcheckProver::prove_with_transcript(&second, &second_sum, &mut prover_transcript).unwrap();
+
+        let mut verifier_transcript = Transcript::new();
+        verifier_transcript.append(b"outer protocol");
+        assert_eq!(
+            MultiComposedSumcheckVerifier::verify_with_transcript(&first, &first_proof, &mut verifier_transcript),
+            Ok(())
+        );
+        assert_eq!(
+            MultiComposedSumcheckVerifier::verify_with_transcript(&second, &second_proof, &mut verifier_transcript),
+            Ok(())
+        );
+
+        // the second proof is bound to everything absorbed before it
+        assert!(MultiComposedSumcheckVerifier::verify(&second, &second_proof).is_err());
+    }
+
+    fn extension_sample_poly() -> Vec<ComposedMultiLinearPolynomial<Fq>> {
+        let to_mle = |v: Vec<u64>| {
+            MultiLinearPolynomialEvaluationForm::new(v.into_iter().map(Fq::from).collect())
+        };
+        let a = to_mle(vec![1, 4, 2, 8, 5, 7, 0, 3]);
+        let b = to_mle(vec![3, 3, 6, 1, 2, 9, 4, 4]);
+        let c = to_mle(vec![0, 1, 1, 0, 1, 0, 0, 1]);
+
+        vec![
+            ComposedMultiLinearPolynomial::new(vec![a.clone(), b.clone(), c.clone()]),
+            ComposedMultiLinearPolynomial::new(vec![a, c]),
+            ComposedMultiLinearPolynomial::new(vec![b]),
+        ]
+    }
+
+    #[test]
+    fn test_sumcheck_over_quadratic_extension() {
+        let poly = extension_sample_poly();
+        let sum = MultiComposedSumcheckProver::calculate_poly_sum(&poly);
+
+        let (proof, challenges) =
+            MultiComposedSumcheckProver::prove_over_extension::<Fq, Fq2>(&poly, &sum).unwrap();
+        assert_eq!(proof.round_polys.len(), 3);
+        // challenges are genuine extension field elements, not lifted base field ones
+        assert!(challenges.iter().any(|c| c.c1 != Fq::from(0)));
+        // the first round only sees the base field witness
+        assert!(proof.round_polys[0].iter().all(|eval| eval.c1 == Fq::from(0)));
+
+        assert_eq!(MultiComposedSumcheckVerifier::verify_over_extension(&poly, &proof), Ok(()));
+    }
+
+    #[test]
+    fn test_sumcheck_over_base_field_as_its_own_extension() {
+        let poly = extension_sample_poly();
+        let sum = MultiComposedSumcheckProver::calculate_poly_sum(&poly);
+
+        let (proof, challenges) =
+            MultiComposedSumcheckProver::prove_over_extension::<Fq, Fq>(&poly, &sum).unwrap();
+        assert_eq!(MultiComposedSumcheckVerifier::verify_over_extension(&poly, &proof), Ok(()));
+
+        // the same rounds as the prime field prover on the same statement
+        let statement = SumcheckStatement::for_oracle(EXTENSION_SUMCHECK_PROTOCOL, poly.as_slice(), sum);
+        let (plain, plain_challenges) =
+            MultiComposedSumcheckProver::prove_statement(&poly, &statement, &mut Transcript::new()).unwrap();
+        assert_eq!(challenges, plain_challenges);
+        assert_eq!(proof.round_polys[0], RoundMessage::from_univariate(&plain.round_polys[0]).unwrap().evaluations);
+    }
+
+    #[test]
+    fn test_sumcheck_over_extension_chained_on_one_transcript() {
+        let poly = extension_sample_poly();
+        let sum = MultiComposedSumcheckProver::calculate_poly_sum(&poly);
+
+        let mut prover_transcript = Transcript::new();
+        prover_transcript.append(b"outer protocol");
+        let (proof, _) = MultiComposedSumcheckProver::prove_over_extension_with_transcript::<Fq, Fq2>(
+            &poly,
+            &sum,
+            &mut prover_transcript,
+        )
+        .unwrap();
+
+        let mut verifier_transcript = Transcript::new();
+        verifier_transcript.append(b"outer protocol");
+        assert_eq!(
+            MultiComposedSumcheckVerifier::verify_over_extension_with_transcript(
+                &poly,
+                &proof,
+                &mut verifier_transcript
+            ),
+            Ok(())
+        );
+        // both sides leave the transcript in the same state for the outer protocol
+        assert_eq!(prover_transcript.sample_challenge(), verifier_transcript.sample_challenge());
+
+        // the proof is bound to everything absorbed before it
+        assert!(MultiComposedSumcheckVerifier::verify_over_extension(&poly, &proof).is_err());
+    }
+
+    #[test]
+    fn test_sumcheck_over_extension_rejects_wrong_sum_and_oversized_round() {
+        let poly = extension_sample_poly();
+        let sum = MultiComposedSumcheckProver::calculate_poly_sum(&poly);
+
+        let (wrong, _) =
+            MultiComposedSumcheckProver::prove_over_extension::<Fq, Fq2>(&poly, &(sum + Fq::from(1)))
+                .unwrap();
+        assert!(MultiComposedSumcheckVerifier::verify_over_extension(&poly, &wrong).is_err());
+
+        let (mut proof, _) =
+            MultiComposedSumcheckProver::prove_over_extension::<Fq, Fq2>(&poly, &sum).unwrap();
+        proof.round_polys[0].push(Fq2::from(1));
+        assert_eq!(
+            MultiComposedSumcheckVerifier::verify_over_extension(&poly, &proof),
+            Err(SumcheckError::DegreeTooHigh { round: 0 })
+        );
+    }
+
+    #[test]
+    fn test_evaluate_mle_at_extension_point() {
+        let mle = MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(3), Fq::from(1), Fq::from(2), Fq::from(5)]);
+        let point = vec![Fq::from(5), Fq::from(6)];
+        let lifted: Vec<Fq2> = point.iter().map(|p| Fq2::from_base_prime_field(*p)).collect();
+
+        assert_eq!(
+            evaluate_mle_at_extension_point(&mle, &lifted),
+            Fq2::from_base_prime_field(mle.evaluation(&point))
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use crate::error::SumcheckError;
+use crate::interactive::{fiat_shamir_challenge, RoundMessage};
+use crate::multi_composedsumcheck::{ComposedSumcheckProof, MultiComposedSumcheckVerifier};
+use crate::statement::{SumcheckStatement, MULTIVARIATE_SUMCHECK_PROTOCOL};
+use ark_ff::PrimeField;
+use polynomial::interface::MultivariatePolynomial;
+use transcript::transcription::Transcript;
+
+/// The statement "`poly` sums to `sum` over the boolean hypercube", with one degree bound per
+/// round taken from the degree of `poly` in that round's variable.
+pub fn multivariate_statement<F: PrimeField, P: MultivariatePolynomial<F> + ?Sized>(
+	poly: &P,
+	sum: F,
+) -> SumcheckStatement<F> {
+	let number_of_variables = poly.number_of_variables();
+	SumcheckStatement::new(MULTIVARIATE_SUMCHECK_PROTOCOL, number_of_variables, 0, sum)
+		.with_round_degrees((0..number_of_variables).map(|i| poly.degree(i)).collect())
+}
+
+/// `sum_{b in {0,1}^k} poly(point[..fixed], b)` for the `k` coordinates after `fixed`.
+fn hypercube_sum<F: PrimeField, P: MultivariatePolynomial<F> + ?Sized>(
+	poly: &P,
+	point: &mut [F],
+	fixed: usize,
+) -> F {
+	let free = point.len() - fixed;
+	let mut sum = F::zero();
+	for b in 0..1usize << free {
+		for (k, coordinate) in point[fixed..].iter_mut().enumerate() {
+			*coordinate = F::from(((b >> (free - 1 - k)) & 1) as u64);
+		}
+		sum += poly.evaluate(point);
+	}
+	sum
+}
+
+/// Sum-check for any multivariate polynomial that can be evaluated, e.g. a
+/// [`MultivariateFunction`](polynomial::multivariate::function::MultivariateFunction) closure.
+///
+/// Round `j` sends `s_j(t) = sum_b poly(r_1, ..., r_{j-1}, t, b)` at `t = 0, ..., d_j`, where
+/// `d_j` is the degree in the `j`-th variable. That is `(d_j + 1) * 2^(n - j)` evaluations of
+/// `poly`, so the prover does `O(d * 2^n)` evaluations in total.
+pub struct MultivariateSumcheckProver {}
+
+impl MultivariateSumcheckProver {
+	pub fn calculate_sum<F: PrimeField, P: MultivariatePolynomial<F> + ?Sized>(poly: &P) -> F {
+		let mut point = vec![F::zero(); poly.number_of_variables()];
+		hypercube_sum(poly, &mut point, 0)
+	}
+
+	pub fn prove<F: PrimeField, P: MultivariatePolynomial<F> + ?Sized>(
+		poly: &P,
+		sum: &F,
+	) -> Result<(ComposedSumcheckProof<F>, Vec<F>), &'static str> {
+		MultivariateSumcheckProver::prove_with_transcript(poly, sum, &mut Transcript::new())
+	}
+
+	/// Like [`MultivariateSumcheckProver::prove`], continuing a transcript owned by an outer
+	/// protocol.
+	pub fn prove_with_transcript<F: PrimeField, P: MultivariatePolynomial<F> + ?Sized>(
+		poly: &P,
+		sum: &F,
+		transcript: &mut Transcript,
+	) -> Result<(ComposedSumcheckProof<F>, Vec<F>), &'static str> {
+		let statement = multivariate_statement(poly, *sum);
+		MultivariateSumcheckProver::prove_statement(poly, &statement, transcript)
+	}
+
+	/// Absorbs `statement` and proves it for `poly`.
+	pub fn prove_statement<F: PrimeField, P: MultivariatePolynomial<F> + ?Sized>(
+		poly: &P,
+		statement: &SumcheckStatement<F>,
+		transcript: &mut Transcript,
+	) -> Result<(ComposedSumcheckProof<F>, Vec<F>), &'static str> {
+		let number_of_variables = poly.number_of_variables();
+		if number_of_variables != statement.number_of_variables {
+			return Err("Statement has a different number of variables than the polynomial");
+		}
+		if (0..number_of_variables).any(|i| poly.degree(i) > statement.degree_bound(i)) {
+			return Err("Polynomial degree exceeds the statement's degree bound");
+		}
+
+		statement.absorb(transcript);
+
+		let mut point = vec![F::zero(); number_of_variables];
+		let mut round_polys = vec![];
+		let mut challenges: Vec<F> = vec![];
+
+		for round in 0..number_of_variables {
+			let evaluations = (0..=poly.degree(round).max(1))
+				.map(|t| {
+					point[round] = F::from(t as u64);
+					hypercube_sum(poly, &mut point, round + 1)
+				})
+				.collect();
+			let round_poly = RoundMessage::new(evaluations).to_univariate();
+
+			let message = RoundMessage::from_univariate(&round_poly)?;
+			let challenge: F = fiat_shamir_challenge(transcript, &message);
+			point[round] = challenge;
+
+			challenges.push(challenge);
+			round_polys.push(round_poly);
+		}
+
+		Ok((ComposedSumcheckProof { round_polys, sum: statement.sum }, challenges))
+	}
+}
+
+pub struct MultivariateSumcheckVerifier {}
+
+impl MultivariateSumcheckVerifier {
+	pub fn verify<F: PrimeField, P: MultivariatePolynomial<F> + ?Sized>(
+		poly: &P,
+		proof: &ComposedSumcheckProof<F>,
+	) -> Result<(), SumcheckError<F>> {
+		Self::verify_with_transcript(poly, proof, &mut Transcript::new())
+	}
+
+	pub fn verify_with_transcript<F: PrimeField, P: MultivariatePolynomial<F> + ?Sized>(
+		poly: &P,
+		proof: &ComposedSumcheckProof<F>,
+		transcript: &mut Transcript,
+	) -> Result<(), SumcheckError<F>> {
+		let statement = multivariate_statement(poly, proof.sum);
+		let sub_claim =
+			MultiComposedSumcheckVerifier::verify_statement(proof, &statement, transcript)?;
+
+		if poly.evaluate(&sub_claim.challenges) != sub_claim.sum {
+			return Err(SumcheckError::FinalEvaluationMismatch);
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::multi_composedsumcheck::MultiComposedSumcheckProver;
+	use crate::util::univariate_degree;
+	use ark_ff::MontConfig;
+	use ark_ff::{Fp64, MontBackend};
+	use polynomial::composed::interface::ComposedMultilinearInterface;
+	use polynomial::composed::multilinear::ComposedMultiLinearPolynomial;
+	use polynomial::multilinear::evaluation_form::MultiLinearPolynomialEvaluationForm;
+	use polynomial::multilinear::interface::MultiLinearPolynomialEvaluationFormTrait;
+	use polynomial::multivariate::function::MultivariateFunction;
+	use polynomial::multivariate::sparse::SparseMultivariatePolynomial;
+
+	#[derive(MontConfig)]
+	#[modulus = "17"]
+	#[generator = "3"]
+	struct FqConfig;
+	type Fq = Fp64<MontBackend<FqConfig, 1>>;
+
+	#[test]
+	fn test_sumcheck_for_closure() {
+		// g(x) = x0^2 * x1 + 3 * x1 * x2^3 + 5
+		let g = MultivariateFunction::with_degrees(vec![2, 1, 3], |x: &[Fq]| {
+			x[0] * x[0] * x[1] + Fq::from(3) * x[1] * x[2] * x[2] * x[2] + Fq::from(5)
+		});
+		let sum = MultivariateSumcheckProver::calculate_sum(&g);
+		// 2 + 2 * 3 + 8 * 5 = 48 = 14 mod 17
+		assert_eq!(sum, Fq::from(14));
+
+		let (proof, challenges) = MultivariateSumcheckProver::prove(&g, &sum).unwrap();
+		assert_eq!(challenges.len(), 3);
+		assert_eq!(MultivariateSumcheckVerifier::verify(&g, &proof), Ok(()));
+
+		let (wrong, _) = MultivariateSumcheckProver::prove(&g, &(sum + Fq::from(1))).unwrap();
+		assert!(MultivariateSumcheckVerifier::verify(&g, &wrong).is_err());
+	}
+
+	#[test]
+	fn test_counting_satisfying_assignments() {
+		// (x0 or x1) and (not x1 or x2), arithmetized clause by clause
+		let or = |a: Fq, b: Fq| Fq::from(1) - (Fq::from(1) - a) * (Fq::from(1) - b);
+		let formula = MultivariateFunction::with_degrees(vec![1, 2, 1], move |x: &[Fq]| {
+			or(x[0], x[1]) * or(Fq::from(1) - x[1], x[2])
+		});
+
+		let count = MultivariateSumcheckProver::calculate_sum(&formula);
+		assert_eq!(count, Fq::from(4));
+
+		let (proof, _) = MultivariateSumcheckProver::prove(&formula, &count).unwrap();
+		assert_eq!(proof.round_polys.len(), 3);
+		assert_eq!(MultivariateSumcheckVerifier::verify(&formula, &proof), Ok(()));
+	}
+
+	#[test]
+	fn test_round_above_variable_degree_is_rejected() {
+		let g = MultivariateFunction::with_degrees(vec![2, 1], |x: &[Fq]| x[0] * x[0] * x[1]);
+		let sum = MultivariateSumcheckProver::calculate_sum(&g);
+		let (proof, _) = MultivariateSumcheckProver::prove(&g, &sum).unwrap();
+
+		// a verifier that only accepts degree one in x0 rejects the first round
+		let linear = MultivariateFunction::new(2, 1, |x: &[Fq]| x[0] * x[0] * x[1]);
+		assert_eq!(
+			MultivariateSumcheckVerifier::verify(&linear, &proof),
+			Err(SumcheckError::DegreeTooHigh { round: 0 })
+		);
+	}
+
+	#[test]
+	fn test_sumcheck_for_sparse_polynomial() {
+		// g(x1, x2, x3) = 2 x1^3 + x1 x3 + x2 x3 sums to 8 + 2 + 2 over the hypercube
+		let g = SparseMultivariatePolynomial::new(
+			3,
+			vec![
+				(Fq::from(2), vec![3, 0, 0]),
+				(Fq::from(1), vec![1, 0, 1]),
+				(Fq::from(1), vec![0, 1, 1]),
+			],
+		);
+		let sum = MultivariateSumcheckProver::calculate_sum(&g);
+		assert_eq!(sum, Fq::from(12));
+
+		let (proof, _) = MultivariateSumcheckProver::prove(&g, &sum).unwrap();
+		assert_eq!(univariate_degree(&proof.round_polys[0]), Ok(3));
+		assert_eq!(MultivariateSumcheckVerifier::verify(&g, &proof), Ok(()));
+	}
+
+	#[test]
+	fn test_sparse_product_of_mles_matches_composed_sum() {
+		let to_mle = |v: Vec<u64>| {
+			MultiLinearPolynomialEvaluationForm::new(v.into_iter().map(Fq::from).collect())
+		};
+		let composed = ComposedMultiLinearPolynomial::new(vec![
+			to_mle(vec![1, 4, 2, 8, 5, 7, 0, 3]),
+			to_mle(vec![3, 3, 6, 1, 2, 9, 4, 4]),
+		]);
+		let sparse = SparseMultivariatePolynomial::from_composed(&composed);
+
+		let sum = MultivariateSumcheckProver::calculate_sum(&sparse);
+		assert_eq!(sum, MultiComposedSumcheckProver::calculate_poly_sum(&vec![composed]));
+
+		let (proof, _) = MultivariateSumcheckProver::prove(&sparse, &sum).unwrap();
+		assert_eq!(MultivariateSumcheckVerifier::verify(&sparse, &proof), Ok(()));
+	}
+}
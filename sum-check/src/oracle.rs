@@ -0,0 +1,223 @@
+use crate::error::SumcheckError;
+use crate::multi_composedsumcheck::composed_mle_to_bytes;
+use ark_ff::PrimeField;
+use polynomial::composed::interface::ComposedMultilinearInterface;
+use polynomial::composed::multilinear::ComposedMultiLinearPolynomial;
+use polynomial::interface::MultivariatePolynomial;
+use polynomial::multilinear::evaluation_form::MultiLinearPolynomialEvaluationForm;
+use polynomial::multilinear::interface::MultiLinearPolynomialEvaluationFormTrait;
+use polynomial::multivariate::sparse::SparseMultivariatePolynomial;
+
+/// Answers the verifier's final query: the value of the summed polynomial at the point made of
+/// the sum-check challenges.
+///
+/// Holding the whole polynomial is the simplest oracle. An oracle backed by a polynomial
+/// commitment only needs the commitment and an opening proof, which keeps the sum-check proof
+/// succinct.
+pub trait EvaluationOracle<F: PrimeField> {
+	fn number_of_variables(&self) -> usize;
+
+	/// The largest degree of the polynomial in any single variable. This is public, a round
+	/// polynomial of higher degree is rejected without being evaluated.
+	fn degree(&self) -> usize;
+
+	fn evaluate(&self, point: &[F]) -> Result<F, SumcheckError<F>>;
+
+	/// Bytes identifying the polynomial, bound into the transcript with the rest of the statement.
+	fn digest(&self) -> Option<Vec<u8>> {
+		None
+	}
+}
+
+impl<F: PrimeField> EvaluationOracle<F> for MultiLinearPolynomialEvaluationForm<F> {
+	fn number_of_variables(&self) -> usize {
+		self.number_of_variables
+	}
+
+	fn degree(&self) -> usize {
+		1
+	}
+
+	fn evaluate(&self, point: &[F]) -> Result<F, SumcheckError<F>> {
+		Ok(self.evaluation(point))
+	}
+
+	fn digest(&self) -> Option<Vec<u8>> {
+		Some(self.to_bytes())
+	}
+}
+
+impl<F: PrimeField> EvaluationOracle<F> for ComposedMultiLinearPolynomial<F> {
+	fn number_of_variables(&self) -> usize {
+		ComposedMultilinearInterface::number_of_variables(self)
+	}
+
+	fn degree(&self) -> usize {
+		self.max_degree()
+	}
+
+	fn evaluate(&self, point: &[F]) -> Result<F, SumcheckError<F>> {
+		Ok(self.evaluation(point))
+	}
+
+	fn digest(&self) -> Option<Vec<u8>> {
+		Some(self.to_bytes())
+	}
+}
+
+/// A sum of composed polynomials, as proved by the multi-composed sum-check.
+impl<F: PrimeField> EvaluationOracle<F> for [ComposedMultiLinearPolynomial<F>] {
+	fn number_of_variables(&self) -> usize {
+		self.first().map_or(0, ComposedMultilinearInterface::number_of_variables)
+	}
+
+	fn degree(&self) -> usize {
+		self.iter().map(|p| p.max_degree()).max().unwrap_or(0)
+	}
+
+	fn evaluate(&self, point: &[F]) -> Result<F, SumcheckError<F>> {
+		Ok(self.iter().map(|p| p.evaluation(point)).sum())
+	}
+
+	fn digest(&self) -> Option<Vec<u8>> {
+		Some(composed_mle_to_bytes(self))
+	}
+}
+
+impl<F: PrimeField> EvaluationOracle<F> for SparseMultivariatePolynomial<F> {
+	fn number_of_variables(&self) -> usize {
+		self.number_of_variables
+	}
+
+	fn degree(&self) -> usize {
+		self.max_degree()
+	}
+
+	fn evaluate(&self, point: &[F]) -> Result<F, SumcheckError<F>> {
+		if point.len() != self.number_of_variables {
+			return Err(SumcheckError::MalformedProof("Point has the wrong number of coordinates"));
+		}
+		Ok(self.evaluation(point))
+	}
+
+	fn digest(&self) -> Option<Vec<u8>> {
+		Some(self.to_bytes())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::composedsumcheck::ComposedSumcheck;
+	use crate::statement::{SumcheckStatement, COMPOSED_SUMCHECK_PROTOCOL};
+	use ark_ff::MontConfig;
+	use ark_ff::{Fp64, MontBackend};
+	use transcript::transcription::Transcript;
+
+	#[derive(MontConfig)]
+	#[modulus = "17"]
+	#[generator = "3"]
+	struct FqConfig;
+	type Fq = Fp64<MontBackend<FqConfig, 1>>;
+
+	/// Stands in for a commitment opening: the verifier only learns the polynomial's shape, a
+	/// commitment to it and its value at the one point it asks for.
+	struct OpenedEvaluation {
+		number_of_variables: usize,
+		degree: usize,
+		commitment: Vec<u8>,
+		point: Vec<Fq>,
+		value: Fq,
+	}
+
+	impl EvaluationOracle<Fq> for OpenedEvaluation {
+		fn number_of_variables(&self) -> usize {
+			self.number_of_variables
+		}
+
+		fn degree(&self) -> usize {
+			self.degree
+		}
+
+		fn evaluate(&self, point: &[Fq]) -> Result<Fq, SumcheckError<Fq>> {
+			if point != self.point.as_slice() {
+				return Err(SumcheckError::MalformedProof("Opening is for a different point"));
+			}
+			Ok(self.value)
+		}
+
+		fn digest(&self) -> Option<Vec<u8>> {
+			Some(self.commitment.clone())
+		}
+	}
+
+	fn sample_poly() -> ComposedMultiLinearPolynomial<Fq> {
+		let to_mle = |v: Vec<u64>| {
+			MultiLinearPolynomialEvaluationForm::new(v.into_iter().map(Fq::from).collect())
+		};
+		ComposedMultiLinearPolynomial::new(vec![
+			to_mle(vec![3, 1, 4, 1, 5, 9, 2, 6]),
+			to_mle(vec![2, 7, 1, 8, 2, 8, 1, 8]),
+		])
+	}
+
+	/// A short binding commitment: the Keccak hash of the polynomial's bytes.
+	fn hash_commitment(poly: &ComposedMultiLinearPolynomial<Fq>) -> Vec<u8> {
+		let mut hasher = Transcript::new();
+		hasher.append(&poly.to_bytes());
+		hasher.sample_challenge().to_vec()
+	}
+
+	#[test]
+	fn test_verify_against_opened_evaluation() {
+		let poly = sample_poly();
+		let sum = ComposedSumcheck::calculate_sum(&poly);
+		let commitment = hash_commitment(&poly);
+		assert_eq!(commitment.len(), 32);
+
+		// the prover binds the same commitment the verifier's oracle reports
+		let statement = SumcheckStatement::for_commitment(
+			COMPOSED_SUMCHECK_PROTOCOL,
+			3,
+			2,
+			sum,
+			commitment.clone(),
+		);
+		let (proof, challenges) = ComposedSumcheck::new(poly.clone())
+			.prove_statement(&statement, &mut Transcript::new())
+			.unwrap();
+
+		let mut opening = OpenedEvaluation {
+			number_of_variables: 3,
+			degree: 2,
+			commitment,
+			value: poly.evaluation(&challenges),
+			point: challenges,
+		};
+		assert_eq!(ComposedSumcheck::verify(&proof, sum, &opening), Ok(()));
+
+		opening.value += Fq::from(1);
+		assert_eq!(
+			ComposedSumcheck::verify(&proof, sum, &opening),
+			Err(SumcheckError::FinalEvaluationMismatch)
+		);
+
+		// a proof bound to the whole polynomial does not verify against its hash
+		opening.value -= Fq::from(1);
+		let (unbound, _) = ComposedSumcheck::new(poly).prove();
+		assert!(ComposedSumcheck::verify(&unbound, sum, &opening).is_err());
+	}
+
+	#[test]
+	fn test_sum_of_composed_polynomials_oracle() {
+		let poly = vec![sample_poly(), sample_poly()];
+		let point = vec![Fq::from(2), Fq::from(5), Fq::from(11)];
+
+		assert_eq!(poly.as_slice().number_of_variables(), 3);
+		assert_eq!(poly.as_slice().degree(), 2);
+		assert_eq!(
+			poly.as_slice().evaluate(&point),
+			Ok(sample_poly().evaluation(&point) * Fq::from(2))
+		);
+	}
+}
@@ -0,0 +1,297 @@
+use crate::error::SumcheckError;
+use crate::multi_composedsumcheck::{
+	ComposedSumcheckProof, MultiComposedSumcheckProver, MultiComposedSumcheckVerifier,
+};
+use crate::statement::{SumcheckStatement, PRODUCTCHECK_PROTOCOL};
+use crate::util::convert_field_to_byte;
+use ark_ff::PrimeField;
+use polynomial::composed::interface::ComposedMultilinearInterface;
+use polynomial::composed::multilinear::ComposedMultiLinearPolynomial;
+use polynomial::multilinear::evaluation_form::MultiLinearPolynomialEvaluationForm;
+use polynomial::multilinear::interface::MultiLinearPolynomialEvaluationFormTrait;
+use polynomial::multilinear::utils::{eq_evaluation, eq_mle};
+use transcript::transcription::Transcript;
+
+/// Proof that the product of all evaluations of a multilinear polynomial equals `product`.
+///
+/// Layer `i` of the product tree holds `2^i` values, each the product of two siblings in layer
+/// `i + 1`; the leaves are the evaluations of the input MLE. `sumcheck_proofs[i]` reduces a claim
+/// on layer `i` to the claims `left_evaluations[i]` and `right_evaluations[i]` on layer `i + 1`.
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct ProductCheckProof<F: PrimeField> {
+	pub product: F,
+	pub sumcheck_proofs: Vec<ComposedSumcheckProof<F>>,
+	pub left_evaluations: Vec<F>,
+	pub right_evaluations: Vec<F>,
+}
+
+/// The claim left over after a product check: the leaf MLE is expected to evaluate to
+/// `expected_evaluation` at `point`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProductCheckSubClaim<F: PrimeField> {
+	pub point: Vec<F>,
+	pub expected_evaluation: F,
+}
+
+/// Builds the layers of the binary product tree, root first and leaves last.
+pub fn product_tree<F: PrimeField>(poly: &MultiLinearPolynomialEvaluationForm<F>) -> Vec<Vec<F>> {
+	let mut layers = vec![poly.evaluations.clone()];
+
+	while layers[layers.len() - 1].len() > 1 {
+		let next: Vec<F> =
+			layers[layers.len() - 1].chunks(2).map(|pair| pair[0] * pair[1]).collect();
+		layers.push(next);
+	}
+
+	layers.reverse();
+	layers
+}
+
+/// The sum-check statement for one layer: `claim` is the sum of `eq(r, x) * left(x) * right(x)`,
+/// a polynomial of degree three.
+fn layer_statement<F: PrimeField>(number_of_variables: usize, claim: F) -> SumcheckStatement<F> {
+	SumcheckStatement::new(PRODUCTCHECK_PROTOCOL, number_of_variables, 3, claim)
+}
+
+pub struct ProductCheckProver {}
+
+impl ProductCheckProver {
+	pub fn prove<F: PrimeField>(
+		poly: &MultiLinearPolynomialEvaluationForm<F>,
+	) -> Result<(ProductCheckProof<F>, Vec<F>), &'static str> {
+		ProductCheckProver::prove_with_transcript(poly, &mut Transcript::new())
+	}
+
+	/// Like [`ProductCheckProver::prove`], continuing a transcript owned by an outer protocol.
+	pub fn prove_with_transcript<F: PrimeField>(
+		poly: &MultiLinearPolynomialEvaluationForm<F>,
+		transcript: &mut Transcript,
+	) -> Result<(ProductCheckProof<F>, Vec<F>), &'static str> {
+		transcript.append(&poly.to_bytes());
+		ProductCheckProver::prove_internal(poly, transcript)
+	}
+
+	pub fn prove_internal<F: PrimeField>(
+		poly: &MultiLinearPolynomialEvaluationForm<F>,
+		transcript: &mut Transcript,
+	) -> Result<(ProductCheckProof<F>, Vec<F>), &'static str> {
+		let layers = product_tree(poly);
+		let product = layers[0][0];
+		transcript.append(&convert_field_to_byte(&product));
+
+		let mut sumcheck_proofs = vec![];
+		let mut left_evaluations = vec![];
+		let mut right_evaluations = vec![];
+
+		let mut claim = product;
+		let mut point: Vec<F> = vec![];
+
+		for layer in layers.iter().skip(1) {
+			// V_i(r) = sum_x eq(r, x) * V_{i+1}(x, 0) * V_{i+1}(x, 1)
+			let left = MultiLinearPolynomialEvaluationForm::new(
+				layer.iter().step_by(2).copied().collect(),
+			);
+			let right = MultiLinearPolynomialEvaluationForm::new(
+				layer.iter().skip(1).step_by(2).copied().collect(),
+			);
+			let layer_poly = ComposedMultiLinearPolynomial::new(vec![
+				eq_mle(&point),
+				left.clone(),
+				right.clone(),
+			]);
+
+			let statement = layer_statement(point.len(), claim);
+			let (sumcheck_proof, challenges) = MultiComposedSumcheckProver::prove_statement(
+				&[layer_poly],
+				&statement,
+				transcript,
+			)?;
+
+			let left_eval = left.evaluation(&challenges);
+			let right_eval = right.evaluation(&challenges);
+			transcript.append(&convert_field_to_byte(&left_eval));
+			transcript.append(&convert_field_to_byte(&right_eval));
+
+			// fold both claims into one on V_{i+1}(challenges, tau)
+			let tau = transcript.transform_challenge_to_field::<F>();
+			claim = left_eval + tau * (right_eval - left_eval);
+			point = challenges;
+			point.push(tau);
+
+			sumcheck_proofs.push(sumcheck_proof);
+			left_evaluations.push(left_eval);
+			right_evaluations.push(right_eval);
+		}
+
+		Ok((
+			ProductCheckProof { product, sumcheck_proofs, left_evaluations, right_evaluations },
+			point,
+		))
+	}
+}
+
+pub struct ProductCheckVerifier {}
+
+impl ProductCheckVerifier {
+	pub fn verify<F: PrimeField>(
+		poly: &MultiLinearPolynomialEvaluationForm<F>,
+		proof: &ProductCheckProof<F>,
+	) -> Result<(), SumcheckError<F>> {
+		Self::verify_with_transcript(poly, proof, &mut Transcript::new())
+	}
+
+	pub fn verify_with_transcript<F: PrimeField>(
+		poly: &MultiLinearPolynomialEvaluationForm<F>,
+		proof: &ProductCheckProof<F>,
+		transcript: &mut Transcript,
+	) -> Result<(), SumcheckError<F>> {
+		transcript.append(&poly.to_bytes());
+		let sub_claim = Self::verify_internal(proof, transcript)?;
+
+		if sub_claim.point.len() != poly.number_of_variables {
+			return Err(SumcheckError::WrongRoundCount {
+				expected: poly.number_of_variables,
+				got: sub_claim.point.len(),
+			});
+		}
+
+		if poly.evaluation(&sub_claim.point) != sub_claim.expected_evaluation {
+			return Err(SumcheckError::FinalEvaluationMismatch);
+		}
+
+		Ok(())
+	}
+
+	/// Checks every layer of the product tree and reduces the product claim to a single
+	/// evaluation of the leaf MLE.
+	pub fn verify_internal<F: PrimeField>(
+		proof: &ProductCheckProof<F>,
+		transcript: &mut Transcript,
+	) -> Result<ProductCheckSubClaim<F>, SumcheckError<F>> {
+		if proof.sumcheck_proofs.len() != proof.left_evaluations.len()
+			|| proof.sumcheck_proofs.len() != proof.right_evaluations.len()
+		{
+			return Err(SumcheckError::MalformedProof(
+				"Product check needs one pair of evaluations per layer",
+			));
+		}
+
+		transcript.append(&convert_field_to_byte(&proof.product));
+
+		let mut claim = proof.product;
+		let mut point: Vec<F> = vec![];
+
+		for (i, sumcheck_proof) in proof.sumcheck_proofs.iter().enumerate() {
+			let statement = layer_statement(point.len(), claim);
+			let sub_claim = MultiComposedSumcheckVerifier::verify_statement(
+				sumcheck_proof,
+				&statement,
+				transcript,
+			)?;
+
+			let left_eval = proof.left_evaluations[i];
+			let right_eval = proof.right_evaluations[i];
+			let expected = eq_evaluation(&point, &sub_claim.challenges) * left_eval * right_eval;
+			if expected != sub_claim.sum {
+				return Err(SumcheckError::FinalEvaluationMismatch);
+			}
+
+			transcript.append(&convert_field_to_byte(&left_eval));
+			transcript.append(&convert_field_to_byte(&right_eval));
+
+			let tau = transcript.transform_challenge_to_field::<F>();
+			claim = left_eval + tau * (right_eval - left_eval);
+			point = sub_claim.challenges;
+			point.push(tau);
+		}
+
+		Ok(ProductCheckSubClaim { point, expected_evaluation: claim })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ark_ff::MontConfig;
+	use ark_ff::{Fp64, MontBackend};
+
+	#[derive(MontConfig)]
+	#[modulus = "17"]
+	#[generator = "3"]
+	struct FqConfig;
+	type Fq = Fp64<MontBackend<FqConfig, 1>>;
+
+	fn to_mle(values: Vec<u64>) -> MultiLinearPolynomialEvaluationForm<Fq> {
+		MultiLinearPolynomialEvaluationForm::new(values.into_iter().map(Fq::from).collect())
+	}
+
+	#[test]
+	fn test_product_tree() {
+		let layers = product_tree(&to_mle(vec![1, 2, 3, 4]));
+		assert_eq!(
+			layers,
+			vec![
+				vec![Fq::from(24)],
+				vec![Fq::from(2), Fq::from(12)],
+				vec![Fq::from(1), Fq::from(2), Fq::from(3), Fq::from(4)]
+			]
+		);
+	}
+
+	#[test]
+	fn test_product_check_proof() {
+		let poly = to_mle(vec![2, 3, 5, 7, 11, 13, 4, 6]);
+		let (proof, point) = ProductCheckProver::prove(&poly).unwrap();
+
+		let expected_product: Fq = poly.evaluations.iter().product();
+		assert_eq!(proof.product, expected_product);
+		assert_eq!(proof.sumcheck_proofs.len(), 3);
+		assert_eq!(point.len(), 3);
+
+		assert_eq!(ProductCheckVerifier::verify(&poly, &proof), Ok(()));
+	}
+
+	#[test]
+	fn test_product_check_sub_claim_matches_leaves() {
+		let poly = to_mle(vec![9, 1, 16, 2, 5, 8, 3, 3, 10, 12, 14, 6, 7, 2, 1, 15]);
+		let (proof, point) = ProductCheckProver::prove(&poly).unwrap();
+
+		let mut transcript = Transcript::new();
+		transcript.append(&poly.to_bytes());
+		let sub_claim = ProductCheckVerifier::verify_internal(&proof, &mut transcript).unwrap();
+
+		assert_eq!(sub_claim.point, point);
+		assert_eq!(poly.evaluation(&sub_claim.point), sub_claim.expected_evaluation);
+	}
+
+	#[test]
+	fn test_product_check_rejects_wrong_product() {
+		let poly = to_mle(vec![2, 3, 5, 7]);
+		let (mut proof, _) = ProductCheckProver::prove(&poly).unwrap();
+		proof.product += Fq::from(1);
+
+		assert!(matches!(
+			ProductCheckVerifier::verify(&poly, &proof),
+			Err(SumcheckError::ClaimedSumMismatch { .. })
+		));
+	}
+
+	#[test]
+	fn test_product_check_rejects_tampered_layer_evaluation() {
+		let poly = to_mle(vec![2, 3, 5, 7, 11, 13, 4, 6]);
+		let (mut proof, _) = ProductCheckProver::prove(&poly).unwrap();
+		proof.left_evaluations[1] += Fq::from(1);
+
+		assert!(ProductCheckVerifier::verify(&poly, &proof).is_err());
+	}
+
+	#[test]
+	fn test_product_check_single_evaluation() {
+		let poly = to_mle(vec![5]);
+		let (proof, point) = ProductCheckProver::prove(&poly).unwrap();
+
+		assert_eq!(proof.product, Fq::from(5));
+		assert!(point.is_empty());
+		assert_eq!(ProductCheckVerifier::verify(&poly, &proof), Ok(()));
+	}
+}
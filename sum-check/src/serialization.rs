@@ -0,0 +1,788 @@
+//! Canonical binary encoding for sum-check proofs.
+//!
+//! Every encoded proof starts with the same header:
+//!
+//! | bytes | content                                   |
+//! |-------|-------------------------------------------|
+//! | 1     | format version, currently `2`             |
+//! | 1     | proof kind, see [`ProofKind`]              |
+//! | 4     | number of rounds, little-endian `u32`      |
+//! | 4     | round polynomial degree, little-endian `u32` |
+//!
+//! Field elements are written as little-endian integers in canonical form, padded to the byte
+//! length of the modulus. Round polynomials in coefficient form are written as their non-zero
+//! terms with strictly increasing powers, and the header degree is the largest of those powers.
+//! Decoding rejects unknown versions, a different proof kind, non-canonical field elements or
+//! round polynomials, declared lengths that do not fit in the input, trailing bytes and inputs
+//! larger than the caller's size limit, so every proof has exactly one encoding.
+
+use crate::composedsumcheck::ComposedSumcheckProof as SingleComposedSumcheckProof;
+use crate::interface::SumCheckProof;
+use crate::multi_composedsumcheck::{ComposedSumcheckProof, SubClaim};
+use crate::util::monomial_power;
+use ark_ff::{BigInteger, PrimeField};
+use polynomial::multilinear::evaluation_form::MultiLinearPolynomialEvaluationForm;
+use polynomial::multilinear::interface::MultiLinearPolynomialEvaluationFormTrait;
+use polynomial::univariate_polynomial::univariate::{UnivariateMonomial, UnivariatePolynomial};
+use std::collections::BTreeMap;
+
+pub const PROOF_FORMAT_VERSION: u8 = 2;
+
+/// Size limit used when the caller has no better bound, 16 MiB.
+pub const DEFAULT_MAX_PROOF_SIZE: usize = 1 << 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofKind {
+	SumCheck = 1,
+	ComposedSumcheck = 2,
+	MultiComposedSumcheck = 3,
+	SubClaim = 4,
+}
+
+impl ProofKind {
+	pub fn name(&self) -> &'static str {
+		match self {
+			ProofKind::SumCheck => "sumcheck",
+			ProofKind::ComposedSumcheck => "composed_sumcheck",
+			ProofKind::MultiComposedSumcheck => "multi_composed_sumcheck",
+			ProofKind::SubClaim => "sub_claim",
+		}
+	}
+}
+
+pub trait ProofEncoding: Sized {
+	/// Encodes `self` in the canonical binary format.
+	fn encode(&self) -> Result<Vec<u8>, &'static str>;
+
+	/// Decodes and validates a proof, rejecting inputs larger than `max_size` bytes.
+	fn decode(bytes: &[u8], max_size: usize) -> Result<Self, &'static str>;
+}
+
+/// Number of bytes used for one field element.
+pub fn field_byte_length<F: PrimeField>() -> usize {
+	(F::MODULUS_BIT_SIZE as usize).div_ceil(8)
+}
+
+/// Writes the primitives of the format. Other proof formats built on this one, e.g. GKR proofs,
+/// use it too so they encode field elements the same way.
+#[derive(Debug, Clone, Default)]
+pub struct ProofWriter {
+	bytes: Vec<u8>,
+}
+
+impl ProofWriter {
+	/// Starts a sum-check proof with its header.
+	pub fn new(
+		kind: ProofKind,
+		number_of_rounds: usize,
+		degree: usize,
+	) -> Result<Self, &'static str> {
+		let mut writer = Self { bytes: vec![PROOF_FORMAT_VERSION, kind as u8] };
+		writer.put_u32(number_of_rounds)?;
+		writer.put_u32(degree)?;
+		Ok(writer)
+	}
+
+	pub fn put_u8(&mut self, value: u8) {
+		self.bytes.push(value);
+	}
+
+	pub fn put_u32(&mut self, value: usize) -> Result<(), &'static str> {
+		let value = u32::try_from(value).map_err(|_| "Length does not fit in a u32")?;
+		self.bytes.extend_from_slice(&value.to_le_bytes());
+		Ok(())
+	}
+
+	pub fn put_bytes(&mut self, bytes: &[u8]) {
+		self.bytes.extend_from_slice(bytes);
+	}
+
+	pub fn put_field<F: PrimeField>(&mut self, element: &F) {
+		let mut bytes = element.into_bigint().to_bytes_le();
+		bytes.resize(field_byte_length::<F>(), 0);
+		self.bytes.extend_from_slice(&bytes);
+	}
+
+	pub fn put_fields<F: PrimeField>(&mut self, elements: &[F]) {
+		for element in elements {
+			self.put_field(element);
+		}
+	}
+
+	pub fn into_bytes(self) -> Vec<u8> {
+		self.bytes
+	}
+}
+
+/// Reads the primitives of the format, rejecting truncated input and non-canonical field
+/// elements.
+#[derive(Debug, Clone)]
+pub struct ProofReader<'a> {
+	bytes: &'a [u8],
+	position: usize,
+}
+
+impl<'a> ProofReader<'a> {
+	/// Checks the size limit and the header, returning the reader with the round count and degree.
+	pub fn new(
+		bytes: &'a [u8],
+		max_size: usize,
+		kind: ProofKind,
+	) -> Result<(Self, usize, usize), &'static str> {
+		let mut reader = Self::with_size_limit(bytes, max_size)?;
+		if reader.get_u8()? != PROOF_FORMAT_VERSION {
+			return Err("Unsupported proof format version");
+		}
+		if reader.get_u8()? != kind as u8 {
+			return Err("Unexpected proof kind");
+		}
+		let number_of_rounds = reader.get_u32()?;
+		let degree = reader.get_u32()?;
+
+		Ok((reader, number_of_rounds, degree))
+	}
+
+	/// A reader over `bytes` that expects no sum-check header, for formats built on this one.
+	pub fn with_size_limit(bytes: &'a [u8], max_size: usize) -> Result<Self, &'static str> {
+		if bytes.len() > max_size {
+			return Err("Proof exceeds the size limit");
+		}
+		Ok(Self { bytes, position: 0 })
+	}
+
+	pub fn remaining(&self) -> usize {
+		self.bytes.len() - self.position
+	}
+
+	pub fn take(&mut self, length: usize) -> Result<&'a [u8], &'static str> {
+		if length > self.remaining() {
+			return Err("Unexpected end of proof");
+		}
+		let slice = &self.bytes[self.position..self.position + length];
+		self.position += length;
+		Ok(slice)
+	}
+
+	pub fn get_u8(&mut self) -> Result<u8, &'static str> {
+		Ok(self.take(1)?[0])
+	}
+
+	pub fn get_u32(&mut self) -> Result<usize, &'static str> {
+		let mut buffer = [0u8; 4];
+		buffer.copy_from_slice(self.take(4)?);
+		Ok(u32::from_le_bytes(buffer) as usize)
+	}
+
+	pub fn get_field<F: PrimeField>(&mut self) -> Result<F, &'static str> {
+		let bytes = self.take(field_byte_length::<F>())?;
+		let element = F::from_le_bytes_mod_order(bytes);
+
+		let mut canonical = element.into_bigint().to_bytes_le();
+		canonical.resize(field_byte_length::<F>(), 0);
+		if canonical != bytes {
+			return Err("Field element is not in canonical form");
+		}
+
+		Ok(element)
+	}
+
+	/// Reads `count` field elements, checking that they fit in the input before allocating.
+	pub fn get_fields<F: PrimeField>(&mut self, count: usize) -> Result<Vec<F>, &'static str> {
+		if count.saturating_mul(field_byte_length::<F>()) > self.remaining() {
+			return Err("Unexpected end of proof");
+		}
+		(0..count).map(|_| self.get_field()).collect()
+	}
+
+	pub fn finish(&self) -> Result<(), &'static str> {
+		if self.remaining() != 0 {
+			return Err("Trailing bytes after proof");
+		}
+		Ok(())
+	}
+}
+
+/// The terms of `round_poly` with equal powers merged, zero coefficients dropped and powers in
+/// increasing order, the only form the binary encoding accepts.
+fn canonical_terms<F: PrimeField>(
+	round_poly: &UnivariatePolynomial<F>,
+) -> Result<Vec<(usize, F)>, &'static str> {
+	let mut terms = BTreeMap::new();
+	for monomial in round_poly.monomial.iter() {
+		*terms.entry(monomial_power(monomial)?).or_insert_with(F::zero) += monomial.coeff;
+	}
+	Ok(terms.into_iter().filter(|(_, coeff)| !coeff.is_zero()).collect())
+}
+
+/// The degree of round polynomials sent as evaluations, taken from the first round.
+fn evaluations_degree<F: PrimeField>(round_polys: &[Vec<F>]) -> Result<usize, &'static str> {
+	match round_polys.first() {
+		Some(round_poly) if round_poly.is_empty() => {
+			Err("Round polynomials need at least one evaluation")
+		},
+		Some(round_poly) => Ok(round_poly.len() - 1),
+		None => Ok(0),
+	}
+}
+
+impl<F: PrimeField> ProofEncoding for SumCheckProof<F> {
+	fn encode(&self) -> Result<Vec<u8>, &'static str> {
+		let mut writer = ProofWriter::new(ProofKind::SumCheck, self.round_poly.len(), 1)?;
+		writer.put_field(&self.sum);
+		for round_poly in self.round_poly.iter() {
+			if round_poly.evaluations.len() != 2 {
+				return Err("Round polynomial must have two evaluations");
+			}
+			writer.put_fields(&round_poly.evaluations);
+		}
+		Ok(writer.into_bytes())
+	}
+
+	fn decode(bytes: &[u8], max_size: usize) -> Result<Self, &'static str> {
+		let (mut reader, number_of_rounds, degree) =
+			ProofReader::new(bytes, max_size, ProofKind::SumCheck)?;
+		if degree != 1 {
+			return Err("Round polynomial degree must be one");
+		}
+
+		let sum = reader.get_field()?;
+		let mut round_poly = Vec::new();
+		for _ in 0..number_of_rounds {
+			round_poly.push(MultiLinearPolynomialEvaluationForm::new(reader.get_fields(2)?));
+		}
+		reader.finish()?;
+
+		Ok(SumCheckProof { round_poly, sum })
+	}
+}
+
+impl<F: PrimeField> ProofEncoding for SingleComposedSumcheckProof<F> {
+	fn encode(&self) -> Result<Vec<u8>, &'static str> {
+		let degree = evaluations_degree(&self.round_polys)?;
+		let mut writer =
+			ProofWriter::new(ProofKind::ComposedSumcheck, self.round_polys.len(), degree)?;
+		for round_poly in self.round_polys.iter() {
+			if round_poly.len() != degree + 1 {
+				return Err("Round polynomials must all have degree + 1 evaluations");
+			}
+			writer.put_fields(round_poly);
+		}
+		Ok(writer.into_bytes())
+	}
+
+	fn decode(bytes: &[u8], max_size: usize) -> Result<Self, &'static str> {
+		let (mut reader, number_of_rounds, degree) =
+			ProofReader::new(bytes, max_size, ProofKind::ComposedSumcheck)?;
+
+		let mut round_polys = Vec::new();
+		for _ in 0..number_of_rounds {
+			round_polys.push(reader.get_fields(degree.saturating_add(1))?);
+		}
+		reader.finish()?;
+
+		Ok(SingleComposedSumcheckProof { round_polys })
+	}
+}
+
+impl<F: PrimeField> ProofEncoding for ComposedSumcheckProof<F> {
+	fn encode(&self) -> Result<Vec<u8>, &'static str> {
+		let rounds = self.round_polys.iter().map(canonical_terms).collect::<Result<Vec<_>, _>>()?;
+		let degree = rounds.iter().flatten().map(|(pow, _)| *pow).max().unwrap_or(0);
+
+		let mut writer = ProofWriter::new(ProofKind::MultiComposedSumcheck, rounds.len(), degree)?;
+		writer.put_field(&self.sum);
+		for terms in rounds.iter() {
+			writer.put_u32(terms.len())?;
+			for (pow, coeff) in terms.iter() {
+				writer.put_u32(*pow)?;
+				writer.put_field(coeff);
+			}
+		}
+		Ok(writer.into_bytes())
+	}
+
+	fn decode(bytes: &[u8], max_size: usize) -> Result<Self, &'static str> {
+		let (mut reader, number_of_rounds, degree) =
+			ProofReader::new(bytes, max_size, ProofKind::MultiComposedSumcheck)?;
+
+		let sum = reader.get_field()?;
+		let mut round_polys = Vec::new();
+		let mut largest_power = 0;
+		for _ in 0..number_of_rounds {
+			let number_of_terms = reader.get_u32()?;
+			if number_of_terms > degree.saturating_add(1) {
+				return Err("Round polynomial has more terms than its degree allows");
+			}
+
+			let mut monomial = Vec::new();
+			let mut previous_pow = None;
+			for _ in 0..number_of_terms {
+				let pow = reader.get_u32()?;
+				if pow > degree {
+					return Err("Round polynomial exceeds the declared degree");
+				}
+				if previous_pow.is_some_and(|previous| pow <= previous) {
+					return Err("Round polynomial powers must be strictly increasing");
+				}
+				let coeff: F = reader.get_field()?;
+				if coeff.is_zero() {
+					return Err("Round polynomial has a zero coefficient");
+				}
+				previous_pow = Some(pow);
+				largest_power = largest_power.max(pow);
+				monomial.push(UnivariateMonomial { coeff, pow: F::from(pow as u64) });
+			}
+			round_polys.push(UnivariatePolynomial { monomial });
+		}
+		if largest_power != degree {
+			return Err("Declared degree does not match the round polynomials");
+		}
+		reader.finish()?;
+
+		Ok(ComposedSumcheckProof { round_polys, sum })
+	}
+}
+
+impl<F: PrimeField> ProofEncoding for SubClaim<F> {
+	fn encode(&self) -> Result<Vec<u8>, &'static str> {
+		let mut writer = ProofWriter::new(ProofKind::SubClaim, self.challenges.len(), 0)?;
+		writer.put_field(&self.sum);
+		writer.put_fields(&self.challenges);
+		Ok(writer.into_bytes())
+	}
+
+	fn decode(bytes: &[u8], max_size: usize) -> Result<Self, &'static str> {
+		let (mut reader, number_of_rounds, _) =
+			ProofReader::new(bytes, max_size, ProofKind::SubClaim)?;
+
+		let sum = reader.get_field()?;
+		let challenges = reader.get_fields(number_of_rounds)?;
+		reader.finish()?;
+
+		Ok(SubClaim { sum, challenges })
+	}
+}
+
+/// Human readable JSON form of the proofs, for debugging only. Field elements are written as
+/// decimal strings and the JSON carries the same header fields as the binary format.
+#[cfg(feature = "json")]
+pub mod json {
+	use super::*;
+	use serde_json::{json, Value};
+
+	pub trait ProofJson: Sized {
+		fn to_json(&self) -> Result<String, &'static str>;
+		fn from_json(json: &str) -> Result<Self, &'static str>;
+	}
+
+	fn header(kind: ProofKind, number_of_rounds: usize, degree: usize) -> Value {
+		json!({
+			"version": PROOF_FORMAT_VERSION,
+			"kind": kind.name(),
+			"num_rounds": number_of_rounds,
+			"degree": degree,
+		})
+	}
+
+	fn parse_header(value: &Value, kind: ProofKind) -> Result<(usize, usize), &'static str> {
+		if value["version"].as_u64() != Some(PROOF_FORMAT_VERSION as u64) {
+			return Err("Unsupported proof format version");
+		}
+		if value["kind"].as_str() != Some(kind.name()) {
+			return Err("Unexpected proof kind");
+		}
+		Ok((get_usize(&value["num_rounds"])?, get_usize(&value["degree"])?))
+	}
+
+	fn get_usize(value: &Value) -> Result<usize, &'static str> {
+		value.as_u64().map(|v| v as usize).ok_or("Expected an integer")
+	}
+
+	fn field_to_json<F: PrimeField>(element: &F) -> Value {
+		Value::String(element.into_bigint().to_string())
+	}
+
+	fn fields_to_json<F: PrimeField>(elements: &[F]) -> Value {
+		Value::Array(elements.iter().map(field_to_json).collect())
+	}
+
+	fn field_from_json<F: PrimeField>(value: &Value) -> Result<F, &'static str> {
+		let text = value.as_str().ok_or("Expected a field element string")?;
+		let element = F::from_str(text).map_err(|_| "Invalid field element")?;
+		if element.into_bigint().to_string() != text {
+			return Err("Field element is not in canonical form");
+		}
+		Ok(element)
+	}
+
+	fn fields_from_json<F: PrimeField>(
+		value: &Value,
+		count: usize,
+	) -> Result<Vec<F>, &'static str> {
+		let array = value.as_array().ok_or("Expected an array")?;
+		if array.len() != count {
+			return Err("Unexpected number of field elements");
+		}
+		array.iter().map(field_from_json).collect()
+	}
+
+	fn parse(json: &str) -> Result<Value, &'static str> {
+		serde_json::from_str(json).map_err(|_| "Invalid JSON")
+	}
+
+	impl<F: PrimeField> ProofJson for ComposedSumcheckProof<F> {
+		fn to_json(&self) -> Result<String, &'static str> {
+			let round_terms =
+				self.round_polys.iter().map(canonical_terms).collect::<Result<Vec<_>, _>>()?;
+			let degree = round_terms.iter().flatten().map(|(pow, _)| *pow).max().unwrap_or(0);
+			let rounds = round_terms
+				.iter()
+				.map(|terms| {
+					Value::Array(
+						terms
+							.iter()
+							.map(
+								|(pow, coeff)| json!({ "pow": pow, "coeff": field_to_json(coeff) }),
+							)
+							.collect(),
+					)
+				})
+				.collect();
+
+			let mut value =
+				header(ProofKind::MultiComposedSumcheck, self.round_polys.len(), degree);
+			value["sum"] = field_to_json(&self.sum);
+			value["round_polys"] = Value::Array(rounds);
+			Ok(value.to_string())
+		}
+
+		fn from_json(json: &str) -> Result<Self, &'static str> {
+			let value = parse(json)?;
+			let (number_of_rounds, degree) =
+				parse_header(&value, ProofKind::MultiComposedSumcheck)?;
+
+			let sum = field_from_json(&value["sum"])?;
+			let rounds = value["round_polys"].as_array().ok_or("Expected an array")?;
+			if rounds.len() != number_of_rounds {
+				return Err("Unexpected number of rounds");
+			}
+
+			let mut round_polys = Vec::new();
+			for round in rounds.iter() {
+				let terms = round.as_array().ok_or("Expected an array")?;
+				if terms.len() > degree.saturating_add(1) {
+					return Err("Round polynomial has more terms than its degree allows");
+				}
+				let mut monomial = Vec::new();
+				for term in terms.iter() {
+					let pow = get_usize(&term["pow"])?;
+					if pow > degree {
+						return Err("Round polynomial exceeds the declared degree");
+					}
+					let coeff = field_from_json(&term["coeff"])?;
+					monomial.push(UnivariateMonomial { coeff, pow: F::from(pow as u64) });
+				}
+				round_polys.push(UnivariatePolynomial { monomial });
+			}
+
+			Ok(ComposedSumcheckProof { round_polys, sum })
+		}
+	}
+
+	impl<F: PrimeField> ProofJson for SingleComposedSumcheckProof<F> {
+		fn to_json(&self) -> Result<String, &'static str> {
+			let degree = evaluations_degree(&self.round_polys)?;
+			let mut value = header(ProofKind::ComposedSumcheck, self.round_polys.len(), degree);
+			value["round_polys"] = Value::Array(
+				self.round_polys.iter().map(|round_poly| fields_to_json(round_poly)).collect(),
+			);
+			Ok(value.to_string())
+		}
+
+		fn from_json(json: &str) -> Result<Self, &'static str> {
+			let value = parse(json)?;
+			let (number_of_rounds, degree) = parse_header(&value, ProofKind::ComposedSumcheck)?;
+
+			let rounds = value["round_polys"].as_array().ok_or("Expected an array")?;
+			if rounds.len() != number_of_rounds {
+				return Err("Unexpected number of rounds");
+			}
+			let round_polys = rounds
+				.iter()
+				.map(|round| fields_from_json(round, degree.saturating_add(1)))
+				.collect::<Result<Vec<_>, _>>()?;
+
+			Ok(SingleComposedSumcheckProof { round_polys })
+		}
+	}
+
+	impl<F: PrimeField> ProofJson for SumCheckProof<F> {
+		fn to_json(&self) -> Result<String, &'static str> {
+			let mut value = header(ProofKind::SumCheck, self.round_poly.len(), 1);
+			value["sum"] = field_to_json(&self.sum);
+			value["round_polys"] = Value::Array(
+				self.round_poly.iter().map(|round| fields_to_json(&round.evaluations)).collect(),
+			);
+			Ok(value.to_string())
+		}
+
+		fn from_json(json: &str) -> Result<Self, &'static str> {
+			let value = parse(json)?;
+			let (number_of_rounds, degree) = parse_header(&value, ProofKind::SumCheck)?;
+			if degree != 1 {
+				return Err("Round polynomial degree must be one");
+			}
+
+			let sum = field_from_json(&value["sum"])?;
+
+			let rounds = value["round_polys"].as_array().ok_or("Expected an array")?;
+			if rounds.len() != number_of_rounds {
+				return Err("Unexpected number of rounds");
+			}
+			let mut round_poly = Vec::new();
+			for round in rounds.iter() {
+				let evaluations = fields_from_json(round, 2)?;
+				round_poly.push(MultiLinearPolynomialEvaluationForm::new(evaluations));
+			}
+
+			Ok(SumCheckProof { round_poly, sum })
+		}
+	}
+
+	impl<F: PrimeField> ProofJson for SubClaim<F> {
+		fn to_json(&self) -> Result<String, &'static str> {
+			let mut value = header(ProofKind::SubClaim, self.challenges.len(), 0);
+			value["sum"] = field_to_json(&self.sum);
+			value["challenges"] = fields_to_json(&self.challenges);
+			Ok(value.to_string())
+		}
+
+		fn from_json(json: &str) -> Result<Self, &'static str> {
+			let value = parse(json)?;
+			let (number_of_rounds, _) = parse_header(&value, ProofKind::SubClaim)?;
+
+			let sum = field_from_json(&value["sum"])?;
+			let challenges = fields_from_json(&value["challenges"], number_of_rounds)?;
+
+			Ok(SubClaim { sum, challenges })
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::composedsumcheck::ComposedSumcheck;
+	use crate::interface::SumCheckInterface;
+	use crate::multi_composedsumcheck::MultiComposedSumcheckProver;
+	use crate::sumcheck::SumCheck;
+	use ark_ff::MontConfig;
+	use ark_ff::{Fp64, MontBackend};
+	use polynomial::composed::interface::ComposedMultilinearInterface;
+	use polynomial::composed::multilinear::ComposedMultiLinearPolynomial;
+
+	#[derive(MontConfig)]
+	#[modulus = "17"]
+	#[generator = "3"]
+	struct FqConfig;
+	type Fq = Fp64<MontBackend<FqConfig, 1>>;
+
+	fn to_mle(values: Vec<u64>) -> MultiLinearPolynomialEvaluationForm<Fq> {
+		MultiLinearPolynomialEvaluationForm::new(values.into_iter().map(Fq::from).collect())
+	}
+
+	fn multi_composed_proof() -> ComposedSumcheckProof<Fq> {
+		let poly1 = to_mle(vec![0, 0, 0, 2]);
+		let poly2 = to_mle(vec![0, 3, 0, 3]);
+		let multi_composed = vec![
+			ComposedMultiLinearPolynomial::new(vec![poly1.clone(), poly2.clone()]),
+			ComposedMultiLinearPolynomial::new(vec![poly2]),
+		];
+		let sum = MultiComposedSumcheckProver::calculate_poly_sum(&multi_composed);
+		MultiComposedSumcheckProver::prove(&multi_composed, &sum).unwrap().0
+	}
+
+	#[test]
+	fn test_multi_composed_proof_round_trip() {
+		let proof = multi_composed_proof();
+		let bytes = proof.encode().unwrap();
+
+		assert_eq!(bytes[0], PROOF_FORMAT_VERSION);
+		assert_eq!(bytes[1], ProofKind::MultiComposedSumcheck as u8);
+		assert_eq!(&bytes[2..6], &2u32.to_le_bytes());
+
+		let decoded = ComposedSumcheckProof::<Fq>::decode(&bytes, DEFAULT_MAX_PROOF_SIZE).unwrap();
+		assert_eq!(decoded, proof);
+	}
+
+	#[test]
+	fn test_sum_check_proof_round_trip() {
+		let mut prover = SumCheck::new(to_mle(vec![0, 0, 2, 7, 3, 3, 6, 11]));
+		prover.calculate_sum();
+		let proof = prover.sum_check_proof();
+
+		let bytes = proof.encode().unwrap();
+		let decoded = SumCheckProof::<Fq>::decode(&bytes, DEFAULT_MAX_PROOF_SIZE).unwrap();
+
+		assert_eq!(decoded.sum, proof.sum);
+		assert_eq!(decoded.round_poly, proof.round_poly);
+		assert_eq!(prover.verify(&decoded, &prover.polynomial), Ok(()));
+	}
+
+	#[test]
+	fn test_composed_proof_round_trip() {
+		let composed = ComposedMultiLinearPolynomial::new(vec![
+			to_mle(vec![3, 3, 5, 5]),
+			to_mle(vec![0, 0, 0, 1]),
+		]);
+		let sumcheck = ComposedSumcheck::new(composed.clone());
+		let (proof, _) = sumcheck.prove();
+
+		let bytes = proof.encode().unwrap();
+		let decoded =
+			SingleComposedSumcheckProof::<Fq>::decode(&bytes, DEFAULT_MAX_PROOF_SIZE).unwrap();
+
+		assert_eq!(decoded, proof);
+		let sum = ComposedSumcheck::calculate_sum(&composed);
+		assert_eq!(ComposedSumcheck::verify(&decoded, sum, &composed), Ok(()));
+	}
+
+	#[test]
+	fn test_encode_rejects_empty_round_polynomial() {
+		let proof = SingleComposedSumcheckProof::<Fq> { round_polys: vec![vec![]] };
+		assert!(proof.encode().is_err());
+	}
+
+	#[test]
+	fn test_sub_claim_round_trip() {
+		let sub_claim = SubClaim { sum: Fq::from(5), challenges: vec![Fq::from(1), Fq::from(16)] };
+		let bytes = sub_claim.encode().unwrap();
+		let decoded = SubClaim::<Fq>::decode(&bytes, DEFAULT_MAX_PROOF_SIZE).unwrap();
+
+		assert_eq!(decoded.sum, sub_claim.sum);
+		assert_eq!(decoded.challenges, sub_claim.challenges);
+	}
+
+	#[test]
+	fn test_decode_rejects_invalid_input() {
+		let bytes = multi_composed_proof().encode().unwrap();
+
+		// size limit
+		assert!(ComposedSumcheckProof::<Fq>::decode(&bytes, bytes.len() - 1).is_err());
+
+		// unknown version
+		let mut wrong_version = bytes.clone();
+		wrong_version[0] = PROOF_FORMAT_VERSION + 1;
+		assert!(
+			ComposedSumcheckProof::<Fq>::decode(&wrong_version, DEFAULT_MAX_PROOF_SIZE).is_err()
+		);
+
+		// wrong kind
+		assert!(SubClaim::<Fq>::decode(&bytes, DEFAULT_MAX_PROOF_SIZE).is_err());
+
+		// truncated and trailing bytes
+		let truncated = &bytes[..bytes.len() - 1];
+		assert!(ComposedSumcheckProof::<Fq>::decode(truncated, DEFAULT_MAX_PROOF_SIZE).is_err());
+		let mut trailing = bytes.clone();
+		trailing.push(0);
+		assert!(ComposedSumcheckProof::<Fq>::decode(&trailing, DEFAULT_MAX_PROOF_SIZE).is_err());
+
+		// declared round count far beyond the input
+		let mut huge_rounds = bytes.clone();
+		huge_rounds[2..6].copy_from_slice(&u32::MAX.to_le_bytes());
+		assert!(ComposedSumcheckProof::<Fq>::decode(&huge_rounds, DEFAULT_MAX_PROOF_SIZE).is_err());
+	}
+
+	/// A multi-composed proof of one round with the given `(pow, coeff)` terms, written as is.
+	fn raw_round_proof(degree: usize, terms: &[(usize, u64)]) -> Vec<u8> {
+		let mut writer = ProofWriter::new(ProofKind::MultiComposedSumcheck, 1, degree).unwrap();
+		writer.put_field(&Fq::from(5));
+		writer.put_u32(terms.len()).unwrap();
+		for (pow, coeff) in terms.iter() {
+			writer.put_u32(*pow).unwrap();
+			writer.put_field(&Fq::from(*coeff));
+		}
+		writer.bytes
+	}
+
+	#[test]
+	fn test_decode_rejects_non_canonical_round_polynomials() {
+		let decode =
+			|bytes: Vec<u8>| ComposedSumcheckProof::<Fq>::decode(&bytes, DEFAULT_MAX_PROOF_SIZE);
+
+		assert!(decode(raw_round_proof(2, &[(0, 3), (2, 1)])).is_ok());
+		assert_eq!(
+			decode(raw_round_proof(2, &[(1, 3), (1, 4)])),
+			Err("Round polynomial powers must be strictly increasing")
+		);
+		assert_eq!(
+			decode(raw_round_proof(2, &[(2, 1), (0, 3)])),
+			Err("Round polynomial powers must be strictly increasing")
+		);
+		assert_eq!(
+			decode(raw_round_proof(2, &[(0, 0), (2, 1)])),
+			Err("Round polynomial has a zero coefficient")
+		);
+		assert_eq!(
+			decode(raw_round_proof(2, &[(0, 3), (1, 1)])),
+			Err("Declared degree does not match the round polynomials")
+		);
+	}
+
+	#[test]
+	fn test_encode_writes_round_polynomials_canonically() {
+		let canonical = ComposedSumcheckProof {
+			round_polys: vec![UnivariatePolynomial {
+				monomial: vec![
+					UnivariateMonomial { coeff: Fq::from(3), pow: Fq::from(0) },
+					UnivariateMonomial { coeff: Fq::from(1), pow: Fq::from(2) },
+				],
+			}],
+			sum: Fq::from(5),
+		};
+		// the same polynomial with terms out of order, a split power and a zero term
+		let scrambled = ComposedSumcheckProof {
+			round_polys: vec![UnivariatePolynomial {
+				monomial: vec![
+					UnivariateMonomial { coeff: Fq::from(1), pow: Fq::from(2) },
+					UnivariateMonomial { coeff: Fq::from(0), pow: Fq::from(1) },
+					UnivariateMonomial { coeff: Fq::from(1), pow: Fq::from(0) },
+					UnivariateMonomial { coeff: Fq::from(2), pow: Fq::from(0) },
+				],
+			}],
+			sum: Fq::from(5),
+		};
+
+		let bytes = canonical.encode().unwrap();
+		assert_eq!(bytes, raw_round_proof(2, &[(0, 3), (2, 1)]));
+		assert_eq!(scrambled.encode().unwrap(), bytes);
+	}
+
+	#[test]
+	fn test_decode_rejects_non_canonical_field_element() {
+		let sub_claim = SubClaim { sum: Fq::from(5), challenges: vec![Fq::from(1)] };
+		let mut bytes = sub_claim.encode().unwrap();
+		// 5 + 17 encodes the same residue but is not canonical
+		bytes[10] = 22;
+
+		assert!(SubClaim::<Fq>::decode(&bytes, DEFAULT_MAX_PROOF_SIZE).is_err());
+	}
+
+	#[cfg(feature = "json")]
+	#[test]
+	fn test_json_round_trip() {
+		use super::json::ProofJson;
+
+		let proof = multi_composed_proof();
+		let json = proof.to_json().unwrap();
+		assert!(json.contains("\"kind\":\"multi_composed_sumcheck\""));
+		assert_eq!(ComposedSumcheckProof::<Fq>::from_json(&json).unwrap(), proof);
+
+		let sub_claim = SubClaim { sum: Fq::from(5), challenges: vec![Fq::from(1), Fq::from(16)] };
+		let decoded = SubClaim::<Fq>::from_json(&sub_claim.to_json().unwrap()).unwrap();
+		assert_eq!(decoded.challenges, sub_claim.challenges);
+
+		let empty_round = SingleComposedSumcheckProof::<Fq> { round_polys: vec![vec![]] };
+		assert!(empty_round.to_json().is_err());
+	}
+}
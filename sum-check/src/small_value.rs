@@ -0,0 +1,194 @@
+//! First sum-check round over small integers.
+//!
+//! Witness tables are mostly bits or small integers. Before the first challenge every entry of
+//! every table is still one of those integers, so the first round polynomial can be computed with
+//! machine integers and only converted to the field at the end. After the first challenge the
+//! folded tables hold arbitrary field elements and the prover switches to field arithmetic.
+
+use crate::interactive::PARALLEL_THRESHOLD;
+use ark_ff::PrimeField;
+use polynomial::composed::multilinear::ComposedMultiLinearPolynomial;
+use rayon::prelude::*;
+use std::ops::Range;
+
+/// The largest entry treated as small. With entries below `2^32` a product of a few round
+/// values stays well inside an `i128`, while anything near a 64-bit modulus would overflow it
+/// after a wasted pass over the tables.
+pub const SMALL_VALUE_BOUND: u64 = u32::MAX as u64;
+
+/// The field element as a `u64`, if it is at most [`SMALL_VALUE_BOUND`].
+pub fn to_small_value<F: PrimeField>(value: &F) -> Option<u64> {
+	let bigint = value.into_bigint();
+	let limbs = bigint.as_ref();
+	if limbs.iter().skip(1).any(|limb| *limb != 0) || limbs[0] > SMALL_VALUE_BOUND {
+		return None;
+	}
+	Some(limbs[0])
+}
+
+/// Every entry of `evaluations` as a `u64`, or `None` as soon as one is not small.
+pub fn to_small_values<F: PrimeField>(evaluations: &[F]) -> Option<Vec<u64>> {
+	if evaluations.len() < PARALLEL_THRESHOLD {
+		return evaluations.iter().map(to_small_value).collect();
+	}
+	evaluations.par_iter().map(to_small_value).collect()
+}
+
+pub fn small_value_to_field<F: PrimeField>(value: i128) -> F {
+	if value >= 0 {
+		F::from(value as u128)
+	} else {
+		-F::from(value.unsigned_abs())
+	}
+}
+
+/// `s(t) = sum_x prod_j f_j(t, x)` at `t = 0, ..., degree` for one product of MLEs whose tables
+/// all hold small integers. Returns `None` if an entry is not small or an intermediate value
+/// overflows an `i128`, in which case the caller falls back to field arithmetic. Large tables are
+/// split into chunks of `x` summed on separate threads.
+pub fn small_value_round_evaluations<F: PrimeField>(
+	poly: &ComposedMultiLinearPolynomial<F>,
+	degree: usize,
+) -> Option<Vec<F>> {
+	let tables: Vec<Vec<u64>> = poly
+		.multilineal_polynomial
+		.iter()
+		.map(|mle| to_small_values(&mle.evaluations))
+		.collect::<Option<_>>()?;
+	let half = tables.first()?.len() / 2;
+
+	let sums = if half < PARALLEL_THRESHOLD {
+		small_value_sums(&tables, 0..half, degree)?
+	} else {
+		(0..half.div_ceil(PARALLEL_THRESHOLD))
+			.into_par_iter()
+			.map(|chunk| {
+				let start = chunk * PARALLEL_THRESHOLD;
+				small_value_sums(&tables, start..(start + PARALLEL_THRESHOLD).min(half), degree)
+			})
+			.try_reduce(
+				|| vec![0i128; degree + 1],
+				|left, right| left.iter().zip(right).map(|(l, r)| l.checked_add(r)).collect(),
+			)?
+	};
+
+	Some(sums.into_iter().map(small_value_to_field).collect())
+}
+
+/// The part of every `s(t)` contributed by the points `x` in `range`.
+fn small_value_sums(tables: &[Vec<u64>], range: Range<usize>, degree: usize) -> Option<Vec<i128>> {
+	let half = tables[0].len() / 2;
+	let mut sums = vec![0i128; degree + 1];
+	for x in range {
+		for (t, sum) in sums.iter_mut().enumerate() {
+			// f_j(t, x) = f_j(0, x) + t * (f_j(1, x) - f_j(0, x))
+			let mut product: i128 = 1;
+			for table in tables.iter() {
+				let low = table[x] as i128;
+				let high = table[x + half] as i128;
+				let value = low.checked_add((t as i128).checked_mul(high - low)?)?;
+				product = product.checked_mul(value)?;
+			}
+			*sum = sum.checked_add(product)?;
+		}
+	}
+	Some(sums)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::interactive::SumcheckProverState;
+	use ark_ff::MontConfig;
+	use ark_ff::{Fp64, MontBackend};
+	use polynomial::composed::interface::ComposedMultilinearInterface;
+	use polynomial::multilinear::evaluation_form::MultiLinearPolynomialEvaluationForm;
+	use polynomial::multilinear::interface::MultiLinearPolynomialEvaluationFormTrait;
+
+	#[derive(MontConfig)]
+	#[modulus = "18446744069414584321"]
+	#[generator = "7"]
+	struct FqConfig;
+	type Fq = Fp64<MontBackend<FqConfig, 1>>;
+
+	fn field_round_evaluations(poly: &ComposedMultiLinearPolynomial<Fq>, degree: usize) -> Vec<Fq> {
+		(0..=degree)
+			.map(|t| {
+				poly.partial_evaluation(Fq::from(t as u64), 0)
+					.elementwise_product()
+					.iter()
+					.sum()
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_small_value_round_matches_field_round() {
+		let to_mle = |v: Vec<u64>| {
+			MultiLinearPolynomialEvaluationForm::new(v.into_iter().map(Fq::from).collect())
+		};
+		let poly = ComposedMultiLinearPolynomial::new(vec![
+			to_mle(vec![0, 1, 1, 0, 1, 1, 0, 1]),
+			to_mle(vec![7, 0, 3, 250, 1, 9, 4, 0]),
+			to_mle(vec![5, 5, 1, 0, 60000, 2, 3, 8]),
+		]);
+
+		for degree in [3, 5] {
+			assert_eq!(
+				small_value_round_evaluations(&poly, degree),
+				Some(field_round_evaluations(&poly, degree))
+			);
+		}
+	}
+
+	#[test]
+	fn test_parallel_small_value_round_matches_field_round() {
+		// 2^16 entries, split across threads, proved through the default first round
+		let to_mle = |seed: u64| {
+			MultiLinearPolynomialEvaluationForm::new(
+				(0..1u64 << 16).map(|i| Fq::from((i * i + seed * i) % 65536)).collect(),
+			)
+		};
+		let poly = ComposedMultiLinearPolynomial::new(vec![to_mle(1), to_mle(2), to_mle(3)]);
+
+		assert_eq!(
+			small_value_round_evaluations(&poly, 3),
+			Some(field_round_evaluations(&poly, 3))
+		);
+
+		let mut plain = SumcheckProverState::new(vec![poly.clone()]);
+		let mut small = SumcheckProverState::new(vec![poly]).with_small_value_first_round();
+		assert_eq!(small.next_round(None), plain.next_round(None));
+		assert_eq!(small.next_round(Some(Fq::from(5))), plain.next_round(Some(Fq::from(5))));
+	}
+
+	#[test]
+	fn test_large_values_fall_back() {
+		let large = MultiLinearPolynomialEvaluationForm::new(vec![-Fq::from(1), Fq::from(2)]);
+		assert_eq!(to_small_value(&Fq::from(42)), Some(42));
+		assert_eq!(to_small_value(&Fq::from(SMALL_VALUE_BOUND)), Some(SMALL_VALUE_BOUND));
+		assert_eq!(to_small_value(&Fq::from(SMALL_VALUE_BOUND + 1)), None);
+
+		let poly = ComposedMultiLinearPolynomial::new(vec![
+			large.clone(),
+			large.clone(),
+			large.clone(),
+			large,
+		]);
+		assert_eq!(small_value_round_evaluations(&poly, 4), None);
+	}
+
+	#[test]
+	fn test_near_modulus_values_are_not_small() {
+		// one limb each, but p - 1 and p - 2 would overflow the i128 products
+		assert_eq!(to_small_value(&-Fq::from(1)), None);
+		assert_eq!(to_small_value(&-Fq::from(2)), None);
+
+		// rejected while reading the tables, before any product is formed
+		let near_modulus =
+			MultiLinearPolynomialEvaluationForm::new(vec![Fq::from(3), -Fq::from(2)]);
+		assert_eq!(to_small_values(&near_modulus.evaluations), None);
+		let poly = ComposedMultiLinearPolynomial::new(vec![near_modulus.clone(), near_modulus]);
+		assert_eq!(small_value_round_evaluations(&poly, 2), None);
+	}
+}
@@ -0,0 +1,190 @@
+//! The public statement a sum-check proves, absorbed into the transcript before any challenge.
+//!
+//! Binding the whole statement stops a prover from picking the claimed sum, or the polynomial,
+//! after it has seen the challenges they lead to.
+
+use crate::oracle::EvaluationOracle;
+use crate::util::convert_field_to_byte;
+use ark_ff::PrimeField;
+use transcript::transcription::Transcript;
+
+pub const SUMCHECK_PROTOCOL: &[u8] = b"sumcheck";
+pub const COMPOSED_SUMCHECK_PROTOCOL: &[u8] = b"composed_sumcheck";
+pub const MULTI_COMPOSED_SUMCHECK_PROTOCOL: &[u8] = b"multi_composed_sumcheck";
+pub const ZEROCHECK_PROTOCOL: &[u8] = b"zerocheck";
+pub const PRODUCTCHECK_PROTOCOL: &[u8] = b"productcheck";
+pub const EXTENSION_SUMCHECK_PROTOCOL: &[u8] = b"extension_sumcheck";
+pub const DOMAIN_SUMCHECK_PROTOCOL: &[u8] = b"domain_sumcheck";
+pub const MULTIVARIATE_SUMCHECK_PROTOCOL: &[u8] = b"multivariate_sumcheck";
+
+/// "The sum of a polynomial in `number_of_variables` variables, of degree at most `degree` in
+/// each, over the boolean hypercube is `sum`". The optional digest identifies the polynomial, e.g.
+/// a commitment to it, and the optional round degrees tighten the bound variable by variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SumcheckStatement<F: PrimeField> {
+	pub protocol: &'static [u8],
+	pub number_of_variables: usize,
+	pub degree: usize,
+	pub sum: F,
+	pub digest: Option<Vec<u8>>,
+	pub round_degrees: Option<Vec<usize>>,
+}
+
+impl<F: PrimeField> SumcheckStatement<F> {
+	pub fn new(protocol: &'static [u8], number_of_variables: usize, degree: usize, sum: F) -> Self {
+		Self { protocol, number_of_variables, degree, sum, digest: None, round_degrees: None }
+	}
+
+	/// The statement about a polynomial the caller has committed to, binding `digest` (e.g. a
+	/// hash or a polynomial commitment) instead of anything derived from the polynomial itself.
+	pub fn for_commitment(
+		protocol: &'static [u8],
+		number_of_variables: usize,
+		degree: usize,
+		sum: F,
+		digest: Vec<u8>,
+	) -> Self {
+		Self::new(protocol, number_of_variables, degree, sum).with_digest(digest)
+	}
+
+	/// The statement about the polynomial behind `oracle`, taking its shape and digest from it.
+	pub fn for_oracle(
+		protocol: &'static [u8],
+		oracle: &(impl EvaluationOracle<F> + ?Sized),
+		sum: F,
+	) -> Self {
+		let number_of_variables = oracle.number_of_variables();
+		match oracle.digest() {
+			Some(digest) => {
+				Self::for_commitment(protocol, number_of_variables, oracle.degree(), sum, digest)
+			},
+			None => Self::new(protocol, number_of_variables, oracle.degree(), sum),
+		}
+	}
+
+	pub fn with_digest(mut self, digest: Vec<u8>) -> Self {
+		self.digest = Some(digest);
+		self
+	}
+
+	/// Bounds the degree of each round polynomial separately, `round_degrees[i]` being the degree
+	/// of the polynomial in its `i`-th variable.
+	pub fn with_round_degrees(mut self, round_degrees: Vec<usize>) -> Self {
+		assert_eq!(
+			round_degrees.len(),
+			self.number_of_variables,
+			"Need one degree bound per variable"
+		);
+		self.degree = round_degrees.iter().copied().max().unwrap_or(0);
+		self.round_degrees = Some(round_degrees);
+		self
+	}
+
+	/// The largest degree the verifier accepts for the round polynomial of `round`. Round
+	/// messages always carry at least two evaluations, so the bound is never below one.
+	pub fn degree_bound(&self, round: usize) -> usize {
+		self.round_degrees
+			.as_ref()
+			.and_then(|degrees| degrees.get(round).copied())
+			.unwrap_or(self.degree)
+			.max(1)
+	}
+
+	/// The degree bound of every round, in order.
+	pub fn degree_bounds(&self) -> Vec<usize> {
+		(0..self.number_of_variables).map(|round| self.degree_bound(round)).collect()
+	}
+
+	/// Length-prefixed encoding, so no two different statements share the same bytes.
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		bytes.extend_from_slice(&(self.protocol.len() as u64).to_le_bytes());
+		bytes.extend_from_slice(self.protocol);
+		bytes.extend_from_slice(&(self.number_of_variables as u64).to_le_bytes());
+		bytes.extend_from_slice(&(self.degree as u64).to_le_bytes());
+
+		let sum = convert_field_to_byte(&self.sum);
+		bytes.extend_from_slice(&(sum.len() as u64).to_le_bytes());
+		bytes.extend_from_slice(&sum);
+
+		match &self.digest {
+			Some(digest) => {
+				bytes.push(1);
+				bytes.extend_from_slice(&(digest.len() as u64).to_le_bytes());
+				bytes.extend_from_slice(digest);
+			},
+			None => bytes.push(0),
+		}
+
+		match &self.round_degrees {
+			Some(degrees) => {
+				bytes.push(1);
+				for degree in degrees.iter() {
+					bytes.extend_from_slice(&(*degree as u64).to_le_bytes());
+				}
+			},
+			None => bytes.push(0),
+		}
+		bytes
+	}
+
+	pub fn absorb(&self, transcript: &mut Transcript) {
+		transcript.append(&self.to_bytes());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ark_ff::MontConfig;
+	use ark_ff::{Fp64, MontBackend};
+
+	#[derive(MontConfig)]
+	#[modulus = "17"]
+	#[generator = "3"]
+	struct FqConfig;
+	type Fq = Fp64<MontBackend<FqConfig, 1>>;
+
+	#[test]
+	fn test_every_statement_field_changes_the_encoding() {
+		let statement = SumcheckStatement::new(SUMCHECK_PROTOCOL, 3, 1, Fq::from(5));
+		let variants = [
+			SumcheckStatement::new(ZEROCHECK_PROTOCOL, 3, 1, Fq::from(5)),
+			SumcheckStatement::new(SUMCHECK_PROTOCOL, 4, 1, Fq::from(5)),
+			SumcheckStatement::new(SUMCHECK_PROTOCOL, 3, 2, Fq::from(5)),
+			SumcheckStatement::new(SUMCHECK_PROTOCOL, 3, 1, Fq::from(6)),
+			statement.clone().with_digest(vec![]),
+			statement.clone().with_round_degrees(vec![1, 1, 1]),
+		];
+
+		for variant in variants.iter() {
+			assert_ne!(variant.to_bytes(), statement.to_bytes());
+		}
+	}
+
+	#[test]
+	fn test_for_commitment_binds_the_given_digest() {
+		let statement =
+			SumcheckStatement::for_commitment(SUMCHECK_PROTOCOL, 3, 1, Fq::from(5), vec![7; 32]);
+		assert_eq!(statement.digest, Some(vec![7; 32]));
+		assert_eq!(
+			statement,
+			SumcheckStatement::new(SUMCHECK_PROTOCOL, 3, 1, Fq::from(5)).with_digest(vec![7; 32])
+		);
+		assert_ne!(
+			statement.to_bytes(),
+			SumcheckStatement::for_commitment(SUMCHECK_PROTOCOL, 3, 1, Fq::from(5), vec![8; 32])
+				.to_bytes()
+		);
+	}
+
+	#[test]
+	fn test_degree_bounds() {
+		let statement = SumcheckStatement::new(SUMCHECK_PROTOCOL, 3, 0, Fq::from(5));
+		assert_eq!(statement.degree_bounds(), vec![1, 1, 1]);
+
+		let statement = statement.with_round_degrees(vec![3, 1, 2]);
+		assert_eq!(statement.degree, 3);
+		assert_eq!(statement.degree_bounds(), vec![3, 1, 2]);
+	}
+}
@@ -3,8 +3,16 @@ use polynomial::multilinear::{
 	evaluation_form::MultiLinearPolynomialEvaluationForm,
 	interface::MultiLinearPolynomialEvaluationFormTrait,
 };
+use crate::error::SumcheckError;
+use crate::interactive::{
+	fiat_shamir_challenge, RoundMessage, SumcheckProverState, SumcheckVerifierState,
+};
 use crate::interface::SumCheckProof;
-use crate::util::convert_field_to_byte;
+use crate::oracle::EvaluationOracle;
+use crate::statement::{SumcheckStatement, SUMCHECK_PROTOCOL};
+use polynomial::composed::{
+	interface::ComposedMultilinearInterface, multilinear::ComposedMultiLinearPolynomial,
+};
 use transcript::transcription::Transcript;
 
 use crate::interface::SumCheckInterface;
@@ -36,63 +44,100 @@ impl<F: PrimeField> SumCheck<F> {
 	}
 }
 
+impl<F: PrimeField> SumCheck<F> {
+	/// Proves `self.sum` on a transcript the caller may already have written to, so an outer
+	/// protocol can run this sum-check as one of its steps. The statement carries the bytes of the
+	/// polynomial as its digest.
+	pub fn prove_with_transcript(&self, transcript: &mut Transcript) -> SumCheckProof<F> {
+		let statement = SumcheckStatement::for_oracle(SUMCHECK_PROTOCOL, &self.polynomial, self.sum);
+		self.prove_statement(&statement, transcript)
+			.expect("statement is built from the polynomial")
+	}
+
+	/// Absorbs `statement`, e.g. one binding a commitment to the polynomial, and proves its sum.
+	pub fn prove_statement(
+		&self,
+		statement: &SumcheckStatement<F>,
+		transcript: &mut Transcript,
+	) -> Result<SumCheckProof<F>, &'static str> {
+		if self.polynomial.number_of_variables != statement.number_of_variables {
+			return Err("Statement has a different number of variables than the polynomial");
+		}
+		if statement.degree < 1 {
+			return Err("Polynomial degree exceeds the statement's degree bound");
+		}
+
+		let mut uni_polys: Vec<MultiLinearPolynomialEvaluationForm<F>> = vec![];
+
+		statement.absorb(transcript);
+
+		let mut prover = SumcheckProverState::new(vec![ComposedMultiLinearPolynomial::new(vec![
+			self.polynomial.clone(),
+		])])
+		.with_small_value_first_round();
+		let mut challenge: Option<F> = None;
+		while !prover.is_finished() {
+			let message = prover.next_round(challenge);
+			challenge = Some(fiat_shamir_challenge(transcript, &message));
+			uni_polys.push(MultiLinearPolynomialEvaluationForm::new(message.evaluations));
+		}
+
+		Ok(SumCheckProof { sum: statement.sum, round_poly: uni_polys })
+	}
+
+	/// Counterpart of [`SumCheck::prove_with_transcript`], the transcript must be in the state
+	/// the prover's was in. Only the proof and the oracle are needed, not the polynomial.
+	pub fn verify_with_transcript(
+		proof: &SumCheckProof<F>,
+		oracle: &dyn EvaluationOracle<F>,
+		transcript: &mut Transcript,
+	) -> Result<(), SumcheckError<F>> {
+		let statement = SumcheckStatement::for_oracle(SUMCHECK_PROTOCOL, oracle, proof.sum);
+		statement.absorb(transcript);
+
+		let number_of_variables = oracle.number_of_variables();
+		if proof.round_poly.len() != number_of_variables {
+			return Err(SumcheckError::WrongRoundCount {
+				expected: number_of_variables,
+				got: proof.round_poly.len(),
+			});
+		}
+
+		let mut verifier = SumcheckVerifierState::new(
+			number_of_variables,
+			proof.sum,
+			statement.degree_bounds(),
+			transcript,
+		);
+		for uni_poly in proof.round_poly.iter() {
+			verifier.receive(&RoundMessage::new(uni_poly.evaluations.clone()))?;
+		}
+
+		let sub_claim = verifier.finalize()?;
+		if oracle.evaluate(&sub_claim.challenges)? != sub_claim.sum {
+			return Err(SumcheckError::FinalEvaluationMismatch);
+		}
+
+		Ok(())
+	}
+}
+
 impl<F: PrimeField> SumCheckInterface<F> for SumCheck<F> {
 	fn calculate_sum(&mut self) {
 	self.sum =	self.polynomial.evaluations.iter().sum()
 	}
 
 	fn sum_check_proof(&mut self) -> SumCheckProof<F> {
-        let mut uni_polys: Vec<MultiLinearPolynomialEvaluationForm<F>> = vec![];
-
-        let mut transcript: Transcript = Transcript::new();
-        let poly_sum_bytes = convert_field_to_byte(&self.sum);
-        transcript.append(&poly_sum_bytes);
-
-        let mut challenges: Vec<F> = vec![];
-        let mut current_poly: MultiLinearPolynomialEvaluationForm<F> = self.polynomial.clone();
-        for _ in 0..self.polynomial.number_of_variables {
-            let uni_poly: MultiLinearPolynomialEvaluationForm<F> = current_poly.split_poly();
-            transcript.append(&uni_poly.to_bytes());
-            uni_polys.push(uni_poly);
-            let random_r: F = transcript.transform_challenge_to_field::<F>();
-            challenges.push(random_r);
-            current_poly = current_poly.partial_evaluation(&random_r, &0);
-        
-        }
-
-
-            SumCheckProof {
-                polynomial: self.polynomial.clone(),
-                sum: self.sum,
-                round_poly: uni_polys,
-            }
-    
+		self.prove_with_transcript(&mut Transcript::new())
 	}
 
-    fn verify(&mut self, proof: &SumCheckProof<F>) -> bool {
-        let mut transcript = Transcript::new();
-        let poly_sum_bytes = convert_field_to_byte(&proof.sum);
-        transcript.append(&poly_sum_bytes);
-
-        let mut claimed_sum = proof.sum;
-        let mut challenges: Vec<F> = vec![];
-
-        let univariate_poly = &proof.round_poly;
-        for i in 0..proof.polynomial.number_of_variables {
-            let uni_poly = &univariate_poly[i];
-            let eval_p0_p1 =
-                uni_poly.evaluation(&vec![F::zero()]) + uni_poly.evaluation(&vec![F::one()]);
-            if eval_p0_p1 != claimed_sum {
-                return false;
-            }
-            transcript.append(&uni_poly.to_bytes());
-            let challenge: F = transcript.transform_challenge_to_field::<F>();
-            challenges.push(challenge);
-            claimed_sum = uni_poly.evaluation(&vec![challenge]);
-        }
-        proof.polynomial.evaluation(challenges.as_slice()) == claimed_sum
-    
-    }
+	fn verify(
+		&self,
+		proof: &SumCheckProof<F>,
+		oracle: &dyn EvaluationOracle<F>,
+	) -> Result<(), SumcheckError<F>> {
+		Self::verify_with_transcript(proof, oracle, &mut Transcript::new())
+	}
 }
 
 #[cfg(test)]
@@ -156,9 +201,55 @@ mod tests {
         let mut prove = SumCheck::new(poly);
         prove.calculate_sum();
         let proof = prove.sum_check_proof();
-        let verifer: bool = prove.verify(&proof);
+        let verifer = prove.verify(&proof, &prove.polynomial);
+
+        assert_eq!(verifer, Ok(()));
+    }
+
+    #[test]
+    fn test_sum_check_rejects_short_proof() {
+        let poly = MultiLinearPolynomialEvaluationForm::new(vec![
+            Fq::from(0),
+            Fq::from(0),
+            Fq::from(2),
+            Fq::from(7),
+            Fq::from(3),
+            Fq::from(3),
+            Fq::from(6),
+            Fq::from(11),
+        ]);
+        let mut prove = SumCheck::new(poly);
+        prove.calculate_sum();
+        let mut proof = prove.sum_check_proof();
+        proof.round_poly.pop();
+
+        assert_eq!(
+            prove.verify(&proof, &prove.polynomial),
+            Err(SumcheckError::WrongRoundCount { expected: 3, got: 2 })
+        );
+    }
+
+    #[test]
+    fn test_sum_check_reports_round_sum_mismatch() {
+        let poly = MultiLinearPolynomialEvaluationForm::new(vec![
+            Fq::from(1),
+            Fq::from(5),
+            Fq::from(2),
+            Fq::from(4),
+        ]);
+        let mut prove = SumCheck::new(poly);
+        prove.calculate_sum();
+        let mut proof = prove.sum_check_proof();
+        proof.sum += Fq::from(1);
 
-        assert_eq!(verifer, true);
+        assert_eq!(
+            prove.verify(&proof, &prove.polynomial),
+            Err(SumcheckError::RoundSumMismatch {
+                round: 0,
+                expected: Fq::from(13),
+                got: Fq::from(12)
+            })
+        );
     }
 
 
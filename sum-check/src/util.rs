@@ -1,4 +1,8 @@
-use ark_ff::{PrimeField,BigInteger};
+use crate::error::SumcheckError;
+use crate::interactive::fold_first_variable;
+use ark_ff::{BigInteger, Field, PrimeField};
+use polynomial::multilinear::evaluation_form::MultiLinearPolynomialEvaluationForm;
+use polynomial::univariate_polynomial::univariate::{UnivariateMonomial, UnivariatePolynomial};
 
 
 pub fn boolean_hypercube<F: PrimeField>(n:usize) -> Vec<Vec<F>>{
@@ -37,3 +41,84 @@ pub fn vec_to_bytes<F: PrimeField>(poly: &Vec<F>) -> Vec<u8> {
     }
     bytes
 }
+
+/// Serializes an extension field element as the big-endian bytes of its base field coefficients.
+pub fn convert_extension_field_to_byte<E: Field>(element: &E) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for coefficient in element.to_base_prime_field_elements() {
+        bytes.extend_from_slice(&coefficient.into_bigint().to_bytes_be());
+    }
+    bytes
+}
+
+/// Evaluates a base field MLE at a point of the extension field.
+pub fn evaluate_mle_at_extension_point<F: PrimeField, E: Field<BasePrimeField = F>>(
+    poly: &MultiLinearPolynomialEvaluationForm<F>,
+    point: &[E],
+) -> E {
+    assert_eq!(
+        point.len(),
+        poly.number_of_variables,
+        "Number of evaluation points must match the number of variables"
+    );
+    let Some((first, rest)) = point.split_first() else {
+        return E::from_base_prime_field(poly.evaluations[0]);
+    };
+
+    let mut evaluations = fold_first_variable(&poly.evaluations, *first, E::from_base_prime_field);
+    for r in rest {
+        evaluations = fold_first_variable(&evaluations, *r, |e| e);
+    }
+    evaluations[0]
+}
+
+/// Evaluates the univariate polynomial given by its evaluations at `0, 1, ..., d` at `point`
+/// using the Lagrange formula. Fails when two of those points coincide in the field, which happens
+/// once there are more evaluations than the characteristic.
+pub fn lagrange_evaluate<E: Field>(evaluations: &[E], point: E) -> Result<E, SumcheckError<E>> {
+    let mut result = E::zero();
+
+    for (i, eval) in evaluations.iter().enumerate() {
+        let x_i = E::from(i as u64);
+        let mut numerator = E::one();
+        let mut denominator = E::one();
+
+        for j in 0..evaluations.len() {
+            if i != j {
+                let x_j = E::from(j as u64);
+                numerator *= point - x_j;
+                denominator *= x_i - x_j;
+            }
+        }
+
+        let inverse = denominator.inverse().ok_or(SumcheckError::MalformedProof(
+            "Round polynomial has more evaluations than the field has points",
+        ))?;
+        result += *eval * numerator * inverse;
+    }
+
+    Ok(result)
+}
+
+/// Converts the power of a monomial to a `usize`, failing for powers that are not small integers.
+pub fn monomial_power<F: PrimeField>(
+    monomial: &UnivariateMonomial<F>,
+) -> Result<usize, &'static str> {
+    let power = monomial.pow.into_bigint();
+    let limbs = power.as_ref();
+    if limbs.iter().skip(1).any(|limb| *limb != 0) || limbs[0] > u32::MAX as u64 {
+        return Err("Monomial power does not fit in a u32");
+    }
+    Ok(limbs[0] as usize)
+}
+
+/// The largest power of any monomial of `poly`, zero coefficients included.
+pub fn univariate_degree<F: PrimeField>(
+    poly: &UnivariatePolynomial<F>,
+) -> Result<usize, &'static str> {
+    let mut degree = 0;
+    for monomial in poly.monomial.iter() {
+        degree = degree.max(monomial_power(monomial)?);
+    }
+    Ok(degree)
+}
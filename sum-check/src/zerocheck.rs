@@ -0,0 +1,258 @@
+use crate::error::SumcheckError;
+use crate::multi_composedsumcheck::{
+	composed_mle_to_bytes, ComposedSumcheckProof, MultiComposedSumcheckProver,
+	MultiComposedSumcheckVerifier,
+};
+use crate::oracle::EvaluationOracle;
+use crate::statement::{SumcheckStatement, ZEROCHECK_PROTOCOL};
+use ark_ff::PrimeField;
+use polynomial::composed::interface::ComposedMultilinearInterface;
+use polynomial::composed::multilinear::ComposedMultiLinearPolynomial;
+use polynomial::multilinear::utils::{eq_evaluation, eq_mle};
+use transcript::transcription::Transcript;
+
+/// The claim left over after a zero-check: `f(point) * eq(r, point)` is expected to equal
+/// `expected_evaluation`, with `eq(r, point)` already computed as `eq_evaluation`.
+///
+/// The eq factor is kept rather than divided out, it is zero for some challenges and an honest
+/// proof must still be accepted then.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZeroCheckSubClaim<F: PrimeField> {
+	pub point: Vec<F>,
+	pub eq_evaluation: F,
+	pub expected_evaluation: F,
+}
+
+impl<F: PrimeField> ZeroCheckSubClaim<F> {
+	/// Whether `evaluation = f(point)` satisfies the claim.
+	pub fn holds_for(&self, evaluation: F) -> bool {
+		evaluation * self.eq_evaluation == self.expected_evaluation
+	}
+}
+
+/// Proves that `f(x) = sum_i prod_j f_ij(x)` vanishes on every point of the boolean hypercube.
+///
+/// The verifier samples `r` from the transcript and both parties run sum-check on `f(x) * eq(r, x)`
+/// with a claimed sum of zero. A non-zero `f` on the hypercube makes this sum non-zero with
+/// overwhelming probability.
+pub struct ZeroCheckProver {}
+
+impl ZeroCheckProver {
+	pub fn prove<F: PrimeField>(
+		poly: &[ComposedMultiLinearPolynomial<F>],
+	) -> Result<(ComposedSumcheckProof<F>, Vec<F>), &'static str> {
+		ZeroCheckProver::prove_with_transcript(poly, &mut Transcript::new())
+	}
+
+	/// Like [`ZeroCheckProver::prove`], continuing a transcript owned by an outer protocol.
+	pub fn prove_with_transcript<F: PrimeField>(
+		poly: &[ComposedMultiLinearPolynomial<F>],
+		transcript: &mut Transcript,
+	) -> Result<(ComposedSumcheckProof<F>, Vec<F>), &'static str> {
+		transcript.append(&composed_mle_to_bytes(poly));
+		ZeroCheckProver::prove_internal(poly, transcript)
+	}
+
+	pub fn prove_internal<F: PrimeField>(
+		poly: &[ComposedMultiLinearPolynomial<F>],
+		transcript: &mut Transcript,
+	) -> Result<(ComposedSumcheckProof<F>, Vec<F>), &'static str> {
+		if poly.is_empty() {
+			return Err("Zero-check needs at least one composed polynomial");
+		}
+
+		let number_of_variables = poly.number_of_variables();
+		let r: Vec<F> = transcript.sample_n_as_field_element(number_of_variables);
+		let eq_r = eq_mle(&r);
+
+		// f(x) * eq(r, x) = sum_i (prod_j f_ij(x)) * eq(r, x)
+		let poly_eq: Vec<ComposedMultiLinearPolynomial<F>> = poly
+			.iter()
+			.map(|p| {
+				let mut mles = p.multilineal_polynomial.clone();
+				mles.push(eq_r.clone());
+				ComposedMultiLinearPolynomial::new(mles)
+			})
+			.collect();
+
+		// the eq factor raises the degree by one
+		let statement = SumcheckStatement::new(
+			ZEROCHECK_PROTOCOL,
+			number_of_variables,
+			poly.degree() + 1,
+			F::zero(),
+		);
+		MultiComposedSumcheckProver::prove_statement(&poly_eq, &statement, transcript)
+	}
+}
+
+pub struct ZeroCheckVerifier {}
+
+impl ZeroCheckVerifier {
+	pub fn verify<F: PrimeField>(
+		poly: &[ComposedMultiLinearPolynomial<F>],
+		proof: &ComposedSumcheckProof<F>,
+	) -> Result<(), SumcheckError<F>> {
+		Self::verify_with_transcript(poly, proof, &mut Transcript::new())
+	}
+
+	pub fn verify_with_transcript<F: PrimeField>(
+		poly: &[ComposedMultiLinearPolynomial<F>],
+		proof: &ComposedSumcheckProof<F>,
+		transcript: &mut Transcript,
+	) -> Result<(), SumcheckError<F>> {
+		transcript.append(&composed_mle_to_bytes(poly));
+		let sub_claim =
+			Self::verify_internal(proof, poly.number_of_variables(), poly.degree(), transcript)?;
+
+		if !sub_claim.holds_for(poly.evaluate(&sub_claim.point)?) {
+			return Err(SumcheckError::FinalEvaluationMismatch);
+		}
+
+		Ok(())
+	}
+
+	/// Checks the sum-check rounds and reduces the zero-check to a single evaluation of `f`, a
+	/// polynomial in `number_of_variables` variables of degree at most `degree` in each.
+	pub fn verify_internal<F: PrimeField>(
+		proof: &ComposedSumcheckProof<F>,
+		number_of_variables: usize,
+		degree: usize,
+		transcript: &mut Transcript,
+	) -> Result<ZeroCheckSubClaim<F>, SumcheckError<F>> {
+		let r: Vec<F> = transcript.sample_n_as_field_element(number_of_variables);
+
+		let statement =
+			SumcheckStatement::new(ZEROCHECK_PROTOCOL, number_of_variables, degree + 1, F::zero());
+		let sub_claim =
+			MultiComposedSumcheckVerifier::verify_statement(proof, &statement, transcript)?;
+
+		// the sum-check sub claim is about f(point) * eq(r, point)
+		Ok(ZeroCheckSubClaim {
+			eq_evaluation: eq_evaluation(&r, &sub_claim.challenges),
+			point: sub_claim.challenges,
+			expected_evaluation: sub_claim.sum,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ark_ff::MontConfig;
+	use ark_ff::{Fp64, MontBackend};
+	use polynomial::multilinear::evaluation_form::MultiLinearPolynomialEvaluationForm;
+	use polynomial::multilinear::interface::MultiLinearPolynomialEvaluationFormTrait;
+
+	#[derive(MontConfig)]
+	#[modulus = "17"]
+	#[generator = "3"]
+	struct FqConfig;
+	type Fq = Fp64<MontBackend<FqConfig, 1>>;
+
+	// a(x) * b(x) - c(x), which vanishes on the hypercube whenever c = a * b
+	fn gate_identity(
+		a: Vec<u64>,
+		b: Vec<u64>,
+		c: Vec<u64>,
+	) -> Vec<ComposedMultiLinearPolynomial<Fq>> {
+		let to_mle = |v: Vec<u64>| {
+			MultiLinearPolynomialEvaluationForm::new(v.into_iter().map(Fq::from).collect())
+		};
+		let minus_c = to_mle(c) * -Fq::from(1);
+
+		vec![
+			ComposedMultiLinearPolynomial::new(vec![to_mle(a), to_mle(b)]),
+			ComposedMultiLinearPolynomial::new(vec![minus_c]),
+		]
+	}
+
+	#[test]
+	fn test_zero_check_accepts_vanishing_polynomial() {
+		let poly = gate_identity(
+			vec![1, 2, 3, 4, 5, 6, 7, 8],
+			vec![2, 2, 3, 1, 0, 4, 1, 2],
+			vec![2, 4, 9, 4, 0, 24, 7, 16],
+		);
+
+		let (proof, challenges) = ZeroCheckProver::prove(&poly).unwrap();
+		assert_eq!(proof.sum, Fq::from(0));
+		assert_eq!(challenges.len(), 3);
+
+		let verify = ZeroCheckVerifier::verify(&poly, &proof);
+		assert_eq!(verify, Ok(()));
+	}
+
+	#[test]
+	fn test_zero_check_sub_claim_matches_polynomial() {
+		let poly = gate_identity(vec![3, 1, 4, 1], vec![5, 9, 2, 6], vec![15, 9, 8, 6]);
+		let (proof, _) = ZeroCheckProver::prove(&poly).unwrap();
+
+		let mut transcript = Transcript::new();
+		transcript.append(&composed_mle_to_bytes(&poly));
+		let sub_claim = ZeroCheckVerifier::verify_internal(&proof, 2, 2, &mut transcript).unwrap();
+
+		let evaluation: Fq = poly.iter().map(|p| p.evaluation(&sub_claim.point)).sum();
+		assert!(sub_claim.holds_for(evaluation));
+	}
+
+	#[test]
+	fn test_zero_check_accepts_challenges_where_eq_vanishes() {
+		let poly = gate_identity(
+			vec![1, 2, 3, 4, 5, 6, 7, 8],
+			vec![2, 2, 3, 1, 0, 4, 1, 2],
+			vec![2, 4, 9, 4, 0, 24, 7, 16],
+		);
+
+		// try outer transcript prefixes until the challenges make eq(r, point) zero
+		let transcript_with = |prefix: u64| {
+			let mut transcript = Transcript::new();
+			transcript.append(&prefix.to_le_bytes());
+			transcript
+		};
+		let prefix = (0u64..1000)
+			.find(|prefix| {
+				let (proof, _) =
+					ZeroCheckProver::prove_with_transcript(&poly, &mut transcript_with(*prefix))
+						.unwrap();
+				let mut transcript = transcript_with(*prefix);
+				transcript.append(&composed_mle_to_bytes(&poly));
+				let sub_claim =
+					ZeroCheckVerifier::verify_internal(&proof, 3, 2, &mut transcript).unwrap();
+				sub_claim.eq_evaluation == Fq::from(0)
+			})
+			.expect("some prefix makes eq(r, point) vanish");
+
+		let (proof, _) =
+			ZeroCheckProver::prove_with_transcript(&poly, &mut transcript_with(prefix)).unwrap();
+		assert_eq!(
+			ZeroCheckVerifier::verify_with_transcript(&poly, &proof, &mut transcript_with(prefix)),
+			Ok(())
+		);
+	}
+
+	#[test]
+	fn test_zero_check_rejects_non_vanishing_polynomial() {
+		// the last gate is wrong: 8 * 2 != 15
+		let poly = gate_identity(
+			vec![1, 2, 3, 4, 5, 6, 7, 8],
+			vec![2, 2, 3, 1, 0, 4, 1, 2],
+			vec![2, 4, 9, 4, 0, 24, 7, 15],
+		);
+
+		let (proof, _) = ZeroCheckProver::prove(&poly).unwrap();
+		assert!(ZeroCheckVerifier::verify(&poly, &proof).is_err());
+	}
+
+	#[test]
+	fn test_zero_check_rejects_non_zero_claimed_sum() {
+		let poly = gate_identity(vec![3, 1, 4, 1], vec![5, 9, 2, 6], vec![15, 9, 8, 6]);
+		let (mut proof, _) = ZeroCheckProver::prove(&poly).unwrap();
+		proof.sum = Fq::from(1);
+
+		assert_eq!(
+			ZeroCheckVerifier::verify(&poly, &proof),
+			Err(SumcheckError::ClaimedSumMismatch { expected: Fq::from(0), got: Fq::from(1) })
+		);
+	}
+}
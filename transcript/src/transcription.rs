@@ -1,5 +1,5 @@
 use sha3::{Keccak256, Digest};
-use ark_ff::{PrimeField};
+use ark_ff::{Field, PrimeField};
 #[derive(Debug,Clone,Default)]
 pub struct Transcript{
    pub hasher:Keccak256
@@ -34,6 +34,14 @@ impl Transcript {
     }
     response
    }
+
+   /// Samples an element of an extension field by drawing one base prime field element per
+   /// coefficient. For a prime field this is the same as `transform_challenge_to_field`.
+   pub fn transform_challenge_to_extension_field<E:Field>(&mut self) -> E{
+    let degree = E::extension_degree() as usize;
+    let coefficients: Vec<E::BasePrimeField> = self.sample_n_as_field_element(degree);
+    E::from_base_prime_field_elems(&coefficients).unwrap()
+   }
 }
 
 